@@ -0,0 +1,57 @@
+//! Record player input for discrete actions like jumping and attacking.
+//! Except for running and blocking, these only fire on the frame the key is pressed.
+//! Input is read every frame, but the simulation runs in fixed ticks,
+//! so presses are held on to until a tick has seen them.
+
+use bevy::prelude::*;
+
+use super::{movement::KeyBindings, replay::ReplayPlayback};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ActionController>();
+    app.add_systems(
+        Update,
+        record_action_controller
+            .in_set(AppSet::RecordInput)
+            .run_if(not(resource_exists::<ReplayPlayback>)),
+    );
+    app.add_systems(FixedPostUpdate, consume_actions);
+}
+
+/// The actions a character wants to perform this frame.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct ActionController {
+    /// Held to run instead of walk.
+    pub run: bool,
+    pub jump: bool,
+    pub attack: bool,
+    /// Held to block.
+    pub block: bool,
+    pub grab: bool,
+}
+
+fn record_action_controller(
+    input: Res<ButtonInput<KeyCode>>,
+    mut controller_query: Query<(&KeyBindings, &mut ActionController)>,
+) {
+    for (bindings, mut controller) in &mut controller_query {
+        *controller = ActionController {
+            run: input.pressed(bindings.run),
+            jump: controller.jump || input.just_pressed(bindings.jump),
+            attack: controller.attack || input.just_pressed(bindings.attack),
+            block: input.pressed(bindings.block),
+            grab: controller.grab || input.just_pressed(bindings.grab),
+        };
+    }
+}
+
+/// Forget presses once a tick has acted on them.
+fn consume_actions(mut controller_query: Query<&mut ActionController>) {
+    for mut controller in &mut controller_query {
+        controller.jump = false;
+        controller.attack = false;
+        controller.grab = false;
+    }
+}
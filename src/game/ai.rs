@@ -0,0 +1,285 @@
+//! A computer-controlled opponent.
+//! The AI writes into the same [`MovementController`] and [`ActionController`] a human player's
+//! keyboard does, so it is bound by exactly the same rules.
+//! It only reconsiders what to do every so often, based on its [`Difficulty`].
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    action::ActionController,
+    combat::{Health, DEPTH_TOLERANCE},
+    movement::MovementController,
+    rng::{GameRng, RngStream},
+    spawn::{enemy::Enemy, player::Player},
+    state::{CharacterState, StateMachine},
+};
+use crate::{ui::localization::Localized, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Opponent, Difficulty, AiController)>();
+    app.init_resource::<Opponent>();
+    app.init_resource::<Difficulty>();
+    app.add_systems(
+        FixedUpdate,
+        (
+            tick_ai_reaction.in_set(AppSet::TickTimers),
+            drive_ai.in_set(AppSet::RecordInput),
+        ),
+    );
+}
+
+/// Who controls [`Player::Player2`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum Opponent {
+    /// A second player on the same keyboard.
+    #[default]
+    Human,
+    /// The AI, at the current [`Difficulty`].
+    Cpu,
+}
+
+impl Opponent {
+    /// Whether the AI controls `player`.
+    pub fn controls(self, player: Player) -> bool {
+        self == Opponent::Cpu && player == Player::Player2
+    }
+}
+
+/// How well the AI plays.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn name(self) -> Localized {
+        Localized::new(match self {
+            Difficulty::Easy => "difficulty-easy",
+            Difficulty::Normal => "difficulty-normal",
+            Difficulty::Hard => "difficulty-hard",
+        })
+    }
+
+    /// How long the AI takes to reconsider what to do.
+    fn reaction_time(self) -> Duration {
+        match self {
+            Difficulty::Easy => Duration::from_millis(600),
+            Difficulty::Normal => Duration::from_millis(350),
+            Difficulty::Hard => Duration::from_millis(150),
+        }
+    }
+
+    /// How likely the AI is to attack when in range.
+    fn attack_chance(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.4,
+            Difficulty::Normal => 0.7,
+            Difficulty::Hard => 0.9,
+        }
+    }
+
+    /// How likely the AI is to block an incoming attack.
+    fn block_chance(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.1,
+            Difficulty::Normal => 0.4,
+            Difficulty::Hard => 0.7,
+        }
+    }
+
+    /// How likely the AI is to grab a blocking target.
+    fn grab_chance(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.0,
+            Difficulty::Normal => 0.3,
+            Difficulty::Hard => 0.8,
+        }
+    }
+
+    /// The fraction of health below which the AI backs off.
+    fn retreat_health(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.15,
+            Difficulty::Normal => 0.25,
+            Difficulty::Hard => 0.3,
+        }
+    }
+}
+
+/// What the AI is currently trying to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum AiBehaviour {
+    /// Walk up to the target.
+    Approach,
+    /// Attack the target, which is in range.
+    Attack,
+    /// Hold block against an incoming attack.
+    Block,
+    /// Grab the target, which is blocking.
+    Grab,
+    /// Run away from the target to avoid getting knocked out.
+    Retreat,
+}
+
+/// Makes a character play on its own.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct AiController {
+    pub difficulty: Difficulty,
+    pub behaviour: AiBehaviour,
+    reaction: Timer,
+}
+
+impl AiController {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            difficulty,
+            behaviour: AiBehaviour::Approach,
+            reaction: Timer::new(difficulty.reaction_time(), TimerMode::Repeating),
+        }
+    }
+}
+
+/// How far in front of its target the AI tries to stand, in world units.
+const ATTACK_DISTANCE: f32 = 140.0;
+/// How close the target has to be for the AI to attack, in world units.
+const ATTACK_RANGE: f32 = 170.0;
+/// How close on the ground plane the AI tries to get before attacking.
+const DEPTH_MARGIN: f32 = DEPTH_TOLERANCE * 0.5;
+
+fn tick_ai_reaction(time: Res<Time>, mut ai_query: Query<&mut AiController>) {
+    for mut ai in &mut ai_query {
+        ai.reaction.tick(time.delta());
+    }
+}
+
+pub(super) fn drive_ai(
+    mut ai_query: Query<(
+        Entity,
+        &mut AiController,
+        &mut MovementController,
+        &mut ActionController,
+        &Transform,
+        &Sprite,
+        &Health,
+        Has<Enemy>,
+    )>,
+    target_query: Query<(Entity, &Transform, &StateMachine, Has<Enemy>), With<Health>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let rng = rng.stream(RngStream::Ai);
+    for (entity, mut ai, mut movement, mut action, transform, sprite, health, enemy) in
+        &mut ai_query
+    {
+        let origin = transform.translation.xy();
+        let target = target_query
+            .iter()
+            // Grunts only go after the players.
+            .filter(|(target, _, target_machine, target_enemy)| {
+                *target != entity
+                    && !(enemy && *target_enemy)
+                    && target_machine.state() != CharacterState::Dead
+            })
+            .map(|(_, target_transform, target_machine, _)| {
+                (
+                    target_transform.translation.xy() - origin,
+                    target_machine.state(),
+                )
+            })
+            .min_by(|(a, _), (b, _)| a.length_squared().total_cmp(&b.length_squared()));
+        let Some((offset, target_state)) = target else {
+            *movement = MovementController::default();
+            *action = ActionController::default();
+            continue;
+        };
+
+        let in_range = offset.x.abs() <= ATTACK_RANGE && offset.y.abs() <= DEPTH_MARGIN;
+        let reacting = ai.reaction.just_finished();
+        if reacting {
+            let difficulty = ai.difficulty;
+            // When cornered, fight back even at low health.
+            ai.behaviour = if !in_range && health.fraction() < difficulty.retreat_health() {
+                AiBehaviour::Retreat
+            } else if in_range
+                && matches!(target_state, CharacterState::Attack(_))
+                && rng.gen_bool(difficulty.block_chance())
+            {
+                AiBehaviour::Block
+            } else if in_range
+                && target_state == CharacterState::Block
+                && rng.gen_bool(difficulty.grab_chance())
+            {
+                AiBehaviour::Grab
+            } else if in_range && rng.gen_bool(difficulty.attack_chance()) {
+                AiBehaviour::Attack
+            } else if in_range {
+                // Hesitate.
+                ai.behaviour
+            } else {
+                AiBehaviour::Approach
+            };
+        }
+
+        // Only turn towards the target when standing still, otherwise it walks into them.
+        let towards = if offset.x < 0.0 { -1.0 } else { 1.0 };
+        let facing_target = sprite.flip_x == (towards < 0.0);
+        let face_target = if facing_target {
+            Vec2::ZERO
+        } else {
+            Vec2::X * towards
+        };
+
+        let mut next_action = ActionController::default();
+        movement.0 = match ai.behaviour {
+            AiBehaviour::Approach => {
+                let goal = offset - Vec2::X * towards * ATTACK_DISTANCE;
+                let intent = Vec2::new(
+                    if goal.x.abs() > ATTACK_RANGE - ATTACK_DISTANCE {
+                        goal.x
+                    } else {
+                        0.0
+                    },
+                    if goal.y.abs() > DEPTH_MARGIN {
+                        goal.y
+                    } else {
+                        0.0
+                    },
+                );
+                next_action.run = goal.length() > 3.0 * ATTACK_DISTANCE;
+                if intent == Vec2::ZERO {
+                    face_target
+                } else {
+                    intent.normalize()
+                }
+            }
+            AiBehaviour::Attack => {
+                next_action.attack = reacting && facing_target;
+                face_target
+            }
+            AiBehaviour::Block => {
+                next_action.block = true;
+                face_target
+            }
+            AiBehaviour::Grab => {
+                next_action.grab = reacting && facing_target;
+                face_target
+            }
+            AiBehaviour::Retreat => {
+                next_action.run = true;
+                Vec2::X * -towards
+            }
+        };
+        *action = next_action;
+    }
+}
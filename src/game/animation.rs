@@ -4,16 +4,20 @@
 //! - [Sprite animation](https://github.com/bevyengine/bevy/blob/latest/examples/2d/sprite_animation.rs)
 //! - [Timers](https://github.com/bevyengine/bevy/blob/latest/examples/time/timers.rs)
 
-use std::time::Duration;
+use std::{f32::consts::FRAC_PI_2, time::Duration};
 
 use bevy::prelude::*;
 
-use super::{audio::sfx::Sfx, movement::MovementController};
+use super::{
+    audio::sfx::Sfx,
+    movement::MovementController,
+    state::{CharacterState, StateMachine},
+};
 use crate::AppSet;
 
 pub(super) fn plugin(app: &mut App) {
     // Animate and play sound effects based on controls.
-    app.register_type::<PlayerAnimation>();
+    app.register_type::<(PlayerAnimation, Tint)>();
     app.add_systems(
         Update,
         (
@@ -29,25 +33,73 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-/// Update the sprite direction and animation state (idling/walking).
+/// Update the sprite direction, pose and animation state based on the character's state.
 fn update_animation_movement(
-    mut player_query: Query<(&MovementController, &mut Sprite, &mut PlayerAnimation)>,
+    mut player_query: Query<(
+        &MovementController,
+        &StateMachine,
+        &mut Sprite,
+        &mut Transform,
+        &mut PlayerAnimation,
+        Option<&Tint>,
+    )>,
 ) {
-    for (controller, mut sprite, mut animation) in &mut player_query {
+    for (controller, machine, mut sprite, mut transform, mut animation, tint) in &mut player_query {
+        let state = machine.state();
+
+        // Only turn around while in control of the character.
         let dx = controller.0.x;
-        if dx != 0.0 {
+        if dx != 0.0 && state.can_act() {
             sprite.flip_x = dx < 0.0;
         }
 
-        let animation_state = if controller.0 == Vec2::ZERO {
-            PlayerAnimationState::Idling
+        // Fall over backwards when knocked down.
+        let lying = matches!(
+            state,
+            CharacterState::KnockedDown | CharacterState::GettingUp | CharacterState::Dead
+        );
+        let rotation = if !lying {
+            Quat::IDENTITY
+        } else if sprite.flip_x {
+            Quat::from_rotation_z(-FRAC_PI_2)
         } else {
-            PlayerAnimationState::Walking
+            Quat::from_rotation_z(FRAC_PI_2)
+        };
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
+
+        let color = match state {
+            CharacterState::HitStun | CharacterState::Grabbed => Color::srgb(1.0, 0.6, 0.6),
+            CharacterState::Dead => Color::srgb(0.5, 0.5, 0.5),
+            _ => tint.map_or(Color::WHITE, |tint| tint.0),
+        };
+        if sprite.color != color {
+            sprite.color = color;
+        }
+
+        let animation_state = match state {
+            CharacterState::Idle => PlayerAnimationState::Idling,
+            CharacterState::Walk | CharacterState::Run => PlayerAnimationState::Walking,
+            CharacterState::Jump => PlayerAnimationState::Jumping,
+            CharacterState::Attack(_) | CharacterState::Grab | CharacterState::Throw => {
+                PlayerAnimationState::Attacking
+            }
+            CharacterState::Block => PlayerAnimationState::Blocking,
+            CharacterState::HitStun | CharacterState::Grabbed => PlayerAnimationState::Hurt,
+            CharacterState::KnockedDown | CharacterState::GettingUp | CharacterState::Dead => {
+                PlayerAnimationState::Lying
+            }
         };
         animation.update_state(animation_state);
     }
 }
 
+/// Colors a character's sprite while it is not hurt or dead.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Tint(pub Color);
+
 /// Update the animation timer.
 fn update_animation_timer(time: Res<Time>, mut query: Query<&mut PlayerAnimation>) {
     for mut animation in &mut query {
@@ -90,6 +142,11 @@ pub struct PlayerAnimation {
 pub enum PlayerAnimationState {
     Idling,
     Walking,
+    Jumping,
+    Attacking,
+    Blocking,
+    Hurt,
+    Lying,
 }
 
 impl PlayerAnimation {
@@ -119,6 +176,76 @@ impl PlayerAnimation {
         }
     }
 
+    /// The number of jumping frames.
+    /// Our atlas has no dedicated row for this, so we hold a walking frame.
+    const JUMPING_FRAMES: usize = 1;
+    /// The duration of each jumping frame.
+    const JUMPING_INTERVAL: Duration = Duration::from_millis(500);
+
+    fn jumping() -> Self {
+        Self {
+            timer: Timer::new(Self::JUMPING_INTERVAL, TimerMode::Repeating),
+            frame: 0,
+            state: PlayerAnimationState::Jumping,
+        }
+    }
+
+    /// The number of attacking frames.
+    /// Our atlas has no dedicated row for this, so we reuse walking frames.
+    const ATTACKING_FRAMES: usize = 2;
+    /// The duration of each attacking frame.
+    const ATTACKING_INTERVAL: Duration = Duration::from_millis(100);
+
+    fn attacking() -> Self {
+        Self {
+            timer: Timer::new(Self::ATTACKING_INTERVAL, TimerMode::Repeating),
+            frame: 0,
+            state: PlayerAnimationState::Attacking,
+        }
+    }
+
+    /// The number of blocking frames.
+    /// Our atlas has no dedicated row for this, so we hold an idle frame.
+    const BLOCKING_FRAMES: usize = 1;
+    /// The duration of each blocking frame.
+    const BLOCKING_INTERVAL: Duration = Duration::from_millis(500);
+
+    fn blocking() -> Self {
+        Self {
+            timer: Timer::new(Self::BLOCKING_INTERVAL, TimerMode::Repeating),
+            frame: 0,
+            state: PlayerAnimationState::Blocking,
+        }
+    }
+
+    /// The number of hurt frames.
+    /// Our atlas has no dedicated row for this, so we hold an idle frame.
+    const HURT_FRAMES: usize = 1;
+    /// The duration of each hurt frame.
+    const HURT_INTERVAL: Duration = Duration::from_millis(500);
+
+    fn hurt() -> Self {
+        Self {
+            timer: Timer::new(Self::HURT_INTERVAL, TimerMode::Repeating),
+            frame: 0,
+            state: PlayerAnimationState::Hurt,
+        }
+    }
+
+    /// The number of lying frames.
+    /// Our atlas has no dedicated row for this, so we rotate an idle frame.
+    const LYING_FRAMES: usize = 1;
+    /// The duration of each lying frame.
+    const LYING_INTERVAL: Duration = Duration::from_millis(500);
+
+    fn lying() -> Self {
+        Self {
+            timer: Timer::new(Self::LYING_INTERVAL, TimerMode::Repeating),
+            frame: 0,
+            state: PlayerAnimationState::Lying,
+        }
+    }
+
     pub fn new() -> Self {
         Self::idling()
     }
@@ -133,6 +260,11 @@ impl PlayerAnimation {
             % match self.state {
                 PlayerAnimationState::Idling => Self::IDLE_FRAMES,
                 PlayerAnimationState::Walking => Self::WALKING_FRAMES,
+                PlayerAnimationState::Jumping => Self::JUMPING_FRAMES,
+                PlayerAnimationState::Attacking => Self::ATTACKING_FRAMES,
+                PlayerAnimationState::Blocking => Self::BLOCKING_FRAMES,
+                PlayerAnimationState::Hurt => Self::HURT_FRAMES,
+                PlayerAnimationState::Lying => Self::LYING_FRAMES,
             };
     }
 
@@ -142,6 +274,11 @@ impl PlayerAnimation {
             match state {
                 PlayerAnimationState::Idling => *self = Self::idling(),
                 PlayerAnimationState::Walking => *self = Self::walking(),
+                PlayerAnimationState::Jumping => *self = Self::jumping(),
+                PlayerAnimationState::Attacking => *self = Self::attacking(),
+                PlayerAnimationState::Blocking => *self = Self::blocking(),
+                PlayerAnimationState::Hurt => *self = Self::hurt(),
+                PlayerAnimationState::Lying => *self = Self::lying(),
             }
         }
     }
@@ -156,6 +293,11 @@ impl PlayerAnimation {
         match self.state {
             PlayerAnimationState::Idling => self.frame,
             PlayerAnimationState::Walking => 6 + self.frame,
+            PlayerAnimationState::Jumping => 7 + self.frame,
+            PlayerAnimationState::Attacking => 9 + self.frame,
+            PlayerAnimationState::Blocking => 1 + self.frame,
+            PlayerAnimationState::Hurt => self.frame,
+            PlayerAnimationState::Lying => self.frame,
         }
     }
 }
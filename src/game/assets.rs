@@ -31,6 +31,12 @@ impl ImageAssets {
         Self(assets)
     }
 
+    /// Empty handles for every image, for running without rendering.
+    #[cfg(feature = "headless")]
+    pub fn placeholders() -> Self {
+        Self(HashMap::from_iter([(ImageAsset::Ducky, Handle::default())]))
+    }
+
     pub fn all_loaded(&self, assets: &Assets<Image>) -> bool {
         self.0.iter().all(|(_, handle)| assets.contains(handle))
     }
@@ -102,6 +108,48 @@ impl SoundtrackAssets {
     }
 }
 
+#[derive(PartialEq, Eq, Hash, Reflect)]
+pub enum FontAsset {
+    /// Blocky letters on a 16 pixel grid, derived from DejaVu Sans Bold.
+    Pixel,
+}
+
+impl FontAsset {
+    /// Whether the font can show `character`. Everything else renders as a box.
+    pub fn has_glyph(&self, character: char) -> bool {
+        match self {
+            FontAsset::Pixel => matches!(
+                character,
+                ' '..='~' | '\u{a0}'..='\u{17f}' | '\u{386}'..='\u{3ce}' | '\u{400}'..='\u{45f}'
+            ),
+        }
+    }
+}
+
+#[derive(Resource, Reflect, Deref, DerefMut)]
+pub struct FontAssets(HashMap<FontAsset, Handle<Font>>);
+
+impl FontAssets {
+    pub fn new(asset_server: &AssetServer) -> Self {
+        let mut assets = HashMap::new();
+        assets.insert(
+            FontAsset::Pixel,
+            asset_server.load("fonts/brawler_pixel.ttf"),
+        );
+        Self(assets)
+    }
+
+    /// Bevy's built-in font for every font, for running without text rendering.
+    #[cfg(feature = "headless")]
+    pub fn placeholders() -> Self {
+        Self(HashMap::from_iter([(FontAsset::Pixel, Handle::default())]))
+    }
+
+    pub fn all_loaded(&self, assets: &Assets<Font>) -> bool {
+        self.0.iter().all(|(_, handle)| assets.contains(handle))
+    }
+}
+
 #[derive(Resource, Reflect, Deref, DerefMut)]
 pub struct UiAssets {
     pub health_bar: Handle<HealthBarUiMaterial>,
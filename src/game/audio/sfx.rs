@@ -1,21 +1,46 @@
 use bevy::{audio::PlaybackMode, prelude::*};
-use rand::prelude::SliceRandom;
+use rand::{prelude::SliceRandom, Rng};
 
-use crate::game::assets::{SfxAsset, SfxAssets};
+use crate::game::{
+    assets::{SfxAsset, SfxAssets},
+    net::Resimulating,
+    rng::{GameRng, RngStream},
+};
 
-pub(super) fn play_sfx(trigger: Trigger<Sfx>, mut commands: Commands, sfxs: Res<SfxAssets>) {
-    let event = trigger.event();
-    let source = match event {
-        Sfx::ButtonHover => &sfxs[&SfxAsset::ButtonHover],
-        Sfx::ButtonPress => &sfxs[&SfxAsset::ButtonPress],
-        Sfx::Step => random_step(&sfxs),
+pub(super) fn play_sfx(
+    trigger: Trigger<Sfx>,
+    mut commands: Commands,
+    sfxs: Res<SfxAssets>,
+    mut rng: ResMut<GameRng>,
+    resimulating: Option<Res<Resimulating>>,
+) {
+    // Rolled back ticks already played their sounds.
+    if resimulating.is_some() {
+        return;
     }
-    .clone_weak();
+    let event = trigger.event();
+    let rng = rng.stream(RngStream::Audio);
+    // We don't have dedicated combat sounds yet,
+    // so we play existing ones slowed down, which makes them sound heavier.
+    let (source, speed) = match event {
+        Sfx::ButtonHover => (&sfxs[&SfxAsset::ButtonHover], 1.0),
+        Sfx::ButtonPress => (&sfxs[&SfxAsset::ButtonPress], 1.0),
+        Sfx::Step => (random_step(&sfxs, rng), 1.0),
+        Sfx::Hit => (random_step(&sfxs, rng), 0.7),
+        Sfx::Block => (&sfxs[&SfxAsset::ButtonPress], 0.6),
+        Sfx::Grab => (&sfxs[&SfxAsset::ButtonHover], 0.8),
+        Sfx::Throw => (random_step(&sfxs, rng), 0.5),
+        Sfx::Pickup => (&sfxs[&SfxAsset::ButtonPress], 1.5),
+    };
     let settings = PlaybackSettings {
         mode: PlaybackMode::Despawn,
+        speed,
         ..default()
     };
-    commands.spawn(AudioSourceBundle { source, settings });
+    commands.spawn(AudioSourceBundle {
+        source: source.clone_weak(),
+        settings,
+    });
 }
 
 /// Play a single sound effect.
@@ -24,15 +49,20 @@ pub enum Sfx {
     ButtonHover,
     ButtonPress,
     Step,
+    Hit,
+    Block,
+    Grab,
+    Throw,
+    Pickup,
 }
 
-fn random_step(sfxs: &SfxAssets) -> &Handle<AudioSource> {
+fn random_step<'a>(sfxs: &'a SfxAssets, rng: &mut impl Rng) -> &'a Handle<AudioSource> {
     [
         &sfxs[&SfxAsset::Step1],
         &sfxs[&SfxAsset::Step2],
         &sfxs[&SfxAsset::Step3],
         &sfxs[&SfxAsset::Step4],
     ]
-    .choose(&mut rand::thread_rng())
+    .choose(rng)
     .unwrap()
 }
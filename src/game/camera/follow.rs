@@ -0,0 +1,131 @@
+//! Keep every player in frame.
+//! The camera moves towards the midpoint of all players and zooms out
+//! as they spread apart, while never showing anything outside the level.
+//! With [`PixelPerfect`] rendering, the zoom goes in whole steps so texels stay on canvas pixels.
+
+use bevy::{prelude::*, render::camera::CameraUpdateSystem, transform::TransformSystem};
+
+use super::pixel_perfect::PixelPerfect;
+use crate::game::spawn::{level::LevelBounds, player::Player};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CameraFollow>();
+    app.add_systems(
+        PostUpdate,
+        follow_players
+            .after(CameraUpdateSystem)
+            .before(super::shake::apply_camera_shake)
+            .before(TransformSystem::TransformPropagate),
+    );
+}
+
+/// Makes a camera frame all [`Player`]s.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct CameraFollow {
+    /// World-space margin kept between the players and the edge of the screen.
+    pub padding: Vec2,
+    /// The smallest projection scale, i.e. how far the camera may zoom in.
+    pub min_scale: f32,
+    /// The largest projection scale, i.e. how far the camera may zoom out.
+    pub max_scale: f32,
+    /// How quickly the camera catches up with its target.
+    /// Higher is snappier, `0.0` disables movement entirely.
+    pub smoothing: f32,
+    /// The smoothed point the camera is looking at, before shake is applied.
+    focus: Vec2,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            padding: Vec2::new(200.0, 150.0),
+            min_scale: 1.0,
+            max_scale: 2.0,
+            smoothing: 5.0,
+            focus: Vec2::ZERO,
+        }
+    }
+}
+
+impl CameraFollow {
+    /// Snap the focus back to the origin.
+    pub fn reset(&mut self) {
+        self.focus = Vec2::ZERO;
+    }
+}
+
+fn follow_players(
+    time: Res<Time>,
+    bounds: Res<LevelBounds>,
+    pixel_perfect: Res<PixelPerfect>,
+    player_query: Query<&Transform, (With<Player>, Without<CameraFollow>)>,
+    mut camera_query: Query<(
+        &mut CameraFollow,
+        &mut OrthographicProjection,
+        &mut Transform,
+    )>,
+) {
+    let players = player_query
+        .iter()
+        .map(|transform| Rect::from_center_size(transform.translation.xy(), Vec2::ZERO))
+        .reduce(|a, b| a.union(b));
+
+    for (mut follow, mut projection, mut transform) in &mut camera_query {
+        // The size of the view in world units at a scale of `1.0`.
+        // Going through the projection makes this independent of the render target.
+        let viewport = projection.area.size() / projection.scale;
+        if let Some(players) = players.filter(|_| viewport.min_element() > 0.0) {
+            // Zoom out far enough to fit every player, but never so far that
+            // the view becomes bigger than the level itself.
+            let needed = players.size() + 2.0 * follow.padding;
+            let max_scale = follow
+                .max_scale
+                .min((bounds.size() / viewport).min_element())
+                .max(follow.min_scale);
+            let target_scale = (needed / viewport)
+                .max_element()
+                .clamp(follow.min_scale, max_scale);
+
+            // Frame-rate independent exponential smoothing.
+            let t = 1.0 - (-follow.smoothing * time.delta_seconds()).exp();
+            projection.scale = if pixel_perfect.enabled {
+                // A fractional scale would stretch some texels over more canvas pixels than others.
+                target_scale
+                    .ceil()
+                    .min(max_scale.floor())
+                    .max(follow.min_scale.ceil())
+            } else {
+                projection.scale.lerp(target_scale, t)
+            };
+            let focus = follow.focus.lerp(players.center(), t);
+
+            // Keep the visible area inside the level.
+            let half_view = viewport * projection.scale / 2.0;
+            follow.focus = clamp_to_bounds(focus, half_view, bounds.0);
+        }
+
+        // Always reposition the camera, as screen shake offsets it every frame.
+        transform.translation = follow.focus.extend(transform.translation.z);
+    }
+}
+
+/// Clamp a camera center so that a view of `half_view` extents stays within `bounds`.
+/// Axes on which the view is larger than the bounds are centered instead.
+fn clamp_to_bounds(center: Vec2, half_view: Vec2, bounds: Rect) -> Vec2 {
+    let min = bounds.min + half_view;
+    let max = bounds.max - half_view;
+    let center_of_bounds = bounds.center();
+    Vec2::new(
+        if min.x <= max.x {
+            center.x.clamp(min.x, max.x)
+        } else {
+            center_of_bounds.x
+        },
+        if min.y <= max.y {
+            center.y.clamp(min.y, max.y)
+        } else {
+            center_of_bounds.y
+        },
+    )
+}
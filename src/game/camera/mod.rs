@@ -0,0 +1,87 @@
+//! The gameplay cameras. The world camera follows the players around the level
+//! and can be shaken to give hits some weight, while the HUD camera draws the UI on top.
+
+pub mod follow;
+pub mod pixel_perfect;
+pub mod shake;
+
+use bevy::prelude::*;
+
+use crate::screen::Screen;
+use follow::CameraFollow;
+use pixel_perfect::{Canvas, PixelPerfect, HUD_LAYER};
+use shake::CameraShake;
+
+pub(super) fn plugin(app: &mut App) {
+    // Spawn the main cameras.
+    app.add_systems(Startup, spawn_cameras);
+    app.add_systems(OnExit(Screen::Playing), reset_camera);
+
+    app.add_plugins((follow::plugin, pixel_perfect::plugin, shake::plugin));
+}
+
+/// The camera that renders the game world.
+#[derive(Component, Debug)]
+pub struct WorldCamera;
+
+/// The camera that renders the UI at full window resolution.
+#[derive(Component, Debug)]
+pub struct HudCamera;
+
+fn spawn_cameras(
+    mut commands: Commands,
+    settings: Res<PixelPerfect>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    // The world camera renders into the canvas in pixel-perfect mode, see `pixel_perfect`.
+    let canvas = images.add(pixel_perfect::new_canvas(settings.resolution));
+
+    commands.spawn((
+        Name::new("World Camera"),
+        Camera2dBundle {
+            camera: Camera {
+                order: -1,
+                ..default()
+            },
+            ..default()
+        },
+        WorldCamera,
+        CameraFollow::default(),
+        CameraShake::default(),
+    ));
+
+    commands.spawn((
+        Name::new("Canvas"),
+        SpriteBundle {
+            texture: canvas,
+            ..default()
+        },
+        Canvas,
+        HUD_LAYER,
+    ));
+
+    commands.spawn((
+        Name::new("HUD Camera"),
+        Camera2dBundle::default(),
+        HudCamera,
+        IsDefaultUiCamera,
+        HUD_LAYER,
+    ));
+}
+
+/// Put the camera back at the origin so menus and the next match start from a clean slate.
+fn reset_camera(
+    mut camera_query: Query<(
+        &mut Transform,
+        &mut OrthographicProjection,
+        &mut CameraFollow,
+        &mut CameraShake,
+    )>,
+) {
+    for (mut transform, mut projection, mut follow, mut shake) in &mut camera_query {
+        *transform = Transform::default();
+        projection.scale = 1.0;
+        follow.reset();
+        shake.trauma = 0.0;
+    }
+}
@@ -0,0 +1,189 @@
+//! Render the game world at a low, fixed resolution and upscale it by an integer factor.
+//! This keeps every texel of our pixel art the same size on screen, no matter the window size.
+//! The HUD is rendered by a separate camera at full window resolution so text stays crisp.
+//! Based on the [pixel grid snap example](https://github.com/bevyengine/bevy/blob/latest/examples/2d/pixel_grid_snap.rs).
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{RenderTarget, ScalingMode},
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        texture::ImageSampler,
+        view::RenderLayers,
+    },
+    transform::TransformSystem,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use super::{HudCamera, WorldCamera};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PixelPerfect>();
+    app.init_resource::<PixelPerfect>();
+    app.add_systems(
+        PostUpdate,
+        (
+            apply_pixel_perfect.run_if(resource_changed::<PixelPerfect>),
+            fit_canvas_to_window,
+            snap_camera_to_pixel_grid
+                .after(super::shake::apply_camera_shake)
+                .before(TransformSystem::TransformPropagate),
+        ),
+    );
+}
+
+/// Render layer only seen by the [`HudCamera`], used for the upscaled canvas.
+pub const HUD_LAYER: RenderLayers = RenderLayers::layer(1);
+
+/// Settings for pixel-perfect rendering of the game world.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct PixelPerfect {
+    /// Whether the world is rendered to the low-resolution canvas
+    /// or straight to the window.
+    pub enabled: bool,
+    /// The virtual resolution of the canvas in pixels.
+    pub resolution: UVec2,
+    /// How many world units one canvas pixel covers.
+    /// This should match the scale our sprites are spawned with.
+    pub world_units_per_pixel: f32,
+}
+
+impl Default for PixelPerfect {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            resolution: UVec2::new(240, 135),
+            world_units_per_pixel: 8.0,
+        }
+    }
+}
+
+/// The sprite that shows the world camera's low-resolution output on the [`HUD_LAYER`].
+#[derive(Component, Debug)]
+pub struct Canvas;
+
+/// Create the render target for the [`WorldCamera`].
+pub(super) fn new_canvas(resolution: UVec2) -> Image {
+    let size = Extent3d {
+        width: resolution.x,
+        height: resolution.y,
+        ..default()
+    };
+    let mut canvas = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("pixel_perfect_canvas"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        // Nearest sampling keeps the pixels sharp when upscaling.
+        sampler: ImageSampler::nearest(),
+        ..default()
+    };
+    // Fill the image with zeroes.
+    canvas.resize(size);
+    canvas
+}
+
+/// Route the world camera to the canvas or the window, depending on the settings.
+fn apply_pixel_perfect(
+    settings: Res<PixelPerfect>,
+    mut images: ResMut<Assets<Image>>,
+    mut canvas_query: Query<(&Handle<Image>, &mut Visibility), With<Canvas>>,
+    mut world_camera_query: Query<
+        (&mut Camera, &mut OrthographicProjection),
+        (With<WorldCamera>, Without<HudCamera>),
+    >,
+    mut hud_camera_query: Query<&mut Camera, (With<HudCamera>, Without<WorldCamera>)>,
+) {
+    let Ok((canvas, mut visibility)) = canvas_query.get_single_mut() else {
+        return;
+    };
+
+    if settings.enabled {
+        if let Some(image) = images.get_mut(canvas) {
+            if image.size() != settings.resolution {
+                image.resize(Extent3d {
+                    width: settings.resolution.x,
+                    height: settings.resolution.y,
+                    ..default()
+                });
+            }
+        }
+    }
+    *visibility = if settings.enabled {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    for (mut camera, mut projection) in &mut world_camera_query {
+        if settings.enabled {
+            let size = settings.resolution.as_vec2() * settings.world_units_per_pixel;
+            camera.target = RenderTarget::Image(canvas.clone());
+            projection.scaling_mode = ScalingMode::Fixed {
+                width: size.x,
+                height: size.y,
+            };
+        } else {
+            camera.target = RenderTarget::Window(WindowRef::Primary);
+            projection.scaling_mode = ScalingMode::WindowSize(1.0);
+        }
+    }
+
+    for mut camera in &mut hud_camera_query {
+        // When the canvas is shown, the area around it is letterboxed with the clear color.
+        // Otherwise the HUD is drawn on top of the world camera's output.
+        camera.clear_color = if settings.enabled {
+            ClearColorConfig::Default
+        } else {
+            ClearColorConfig::None
+        };
+    }
+}
+
+/// Upscale the canvas by the largest integer factor that still fits the window.
+fn fit_canvas_to_window(
+    settings: Res<PixelPerfect>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut canvas_query: Query<&mut Transform, With<Canvas>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    // Work in physical pixels, so the factor is an integer on high-DPI screens too.
+    let factor = (window.physical_size() / settings.resolution.max(UVec2::ONE))
+        .min_element()
+        .max(1) as f32;
+    let scale = Vec3::splat(factor / window.scale_factor()).with_z(1.0);
+
+    for mut transform in &mut canvas_query {
+        if transform.scale != scale {
+            transform.scale = scale;
+        }
+    }
+}
+
+/// Snap the world camera to whole canvas pixels so the scene doesn't shimmer as it moves.
+fn snap_camera_to_pixel_grid(
+    settings: Res<PixelPerfect>,
+    mut camera_query: Query<(&OrthographicProjection, &mut Transform), With<WorldCamera>>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    for (projection, mut transform) in &mut camera_query {
+        let pixel = settings.world_units_per_pixel * projection.scale;
+        let snapped = (transform.translation.xy() / pixel).round() * pixel;
+        transform.translation = snapped.extend(transform.translation.z);
+    }
+}
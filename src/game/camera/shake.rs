@@ -0,0 +1,79 @@
+//! Trauma-based screen shake, see
+//! [Math for Game Programmers: Juicing Your Cameras With Math](https://www.youtube.com/watch?v=tu-Qe66AvtY).
+
+use bevy::{prelude::*, transform::TransformSystem};
+use rand::Rng;
+
+use crate::game::rng::{GameRng, RngStream};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CameraShake>();
+    app.observe(add_trauma);
+    app.add_systems(
+        PostUpdate,
+        apply_camera_shake.before(TransformSystem::TransformPropagate),
+    );
+}
+
+/// Shake the camera, e.g. when a heavy hit lands.
+/// The amount of trauma stacks and is capped at `1.0`.
+#[derive(Event, Debug)]
+pub struct ShakeCamera {
+    pub trauma: f32,
+}
+
+/// Tracks how much a camera is currently shaking.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct CameraShake {
+    /// Current shake intensity, between `0.0` and `1.0`.
+    pub trauma: f32,
+    /// How much trauma is lost per second.
+    pub decay: f32,
+    /// Translation offset in world units at full trauma.
+    pub max_offset: f32,
+    /// Rotation in radians at full trauma.
+    pub max_roll: f32,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.5,
+            max_offset: 24.0,
+            max_roll: 0.05,
+        }
+    }
+}
+
+fn add_trauma(trigger: Trigger<ShakeCamera>, mut shake_query: Query<&mut CameraShake>) {
+    let trauma = trigger.event().trauma;
+    for mut shake in &mut shake_query {
+        shake.trauma = (shake.trauma + trauma).min(1.0);
+    }
+}
+
+/// Offset the camera by a random amount proportional to its trauma.
+/// This runs after [`CameraFollow`](super::follow::CameraFollow) has positioned the camera
+/// for the frame, so the offset never accumulates.
+pub(super) fn apply_camera_shake(
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    mut shake_query: Query<(&mut CameraShake, &mut Transform)>,
+) {
+    let rng = rng.stream(RngStream::Camera);
+    for (mut shake, mut transform) in &mut shake_query {
+        // Squaring makes small amounts of trauma barely noticeable and big ones violent.
+        let intensity = shake.trauma * shake.trauma;
+        let offset = Vec2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0))
+            * shake.max_offset
+            * intensity;
+        let roll = rng.gen_range(-1.0..=1.0) * shake.max_roll * intensity;
+
+        transform.translation += offset.extend(0.0);
+        transform.rotation = Quat::from_rotation_z(roll);
+
+        shake.trauma = (shake.trauma - shake.decay * time.delta_seconds()).max(0.0);
+    }
+}
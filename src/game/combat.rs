@@ -0,0 +1,402 @@
+//! Attacks, hits and health.
+//! Attacks are started by the [`StateMachine`] and check for victims in front of the attacker
+//! while they are active. Landing a hit triggers [`Hit`], which observers turn into damage,
+//! knockback, hit-stun and screen shake.
+
+use std::time::Duration;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+use super::{
+    audio::sfx::Sfx,
+    camera::shake::ShakeCamera,
+    depth::Elevation,
+    jump::Airborne,
+    spawn::enemy::Enemy,
+    state::{CharacterState, StateEntered, StateMachine},
+    waves::GameMode,
+};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Health, HitVictims, Knockback)>();
+    app.add_systems(
+        FixedUpdate,
+        (detect_hits, apply_knockback)
+            .chain()
+            .in_set(AppSet::Update),
+    );
+    app.observe(start_attack);
+    app.observe(apply_hit);
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// How much health is left, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f32 {
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+}
+
+/// Every attack a character can perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum AttackKind {
+    /// A quick jab on the ground. Starts the light-light-heavy combo.
+    Punch,
+    /// The second hit of the combo.
+    Hook,
+    /// The heavy combo finisher.
+    Uppercut,
+    /// Down, forward and attack dash forward shoulder first.
+    Lunge,
+    /// A kick performed in the air.
+    JumpKick,
+    /// Holding down while attacking in the air plunges feet first into the ground.
+    DiveKick,
+    /// Throwing a grabbed character. This is never chosen as a regular attack,
+    /// see [`grab`](super::grab) instead.
+    Throw,
+    /// Tossing a held item. The item hits instead of the attacker,
+    /// see [`pickup`](super::pickup).
+    Toss,
+}
+
+impl AttackKind {
+    /// Pick the attack to perform based on whether the attacker is in the air
+    /// and which direction they are holding.
+    /// Grounded attacks are usually picked from the [`MoveList`](super::combo::MoveList) instead.
+    pub fn choose(airborne: bool, direction: Vec2) -> Self {
+        match airborne {
+            false => AttackKind::Punch,
+            true if direction.y < 0.0 => AttackKind::DiveKick,
+            true => AttackKind::JumpKick,
+        }
+    }
+
+    /// Whether this attack can only be performed in the air.
+    /// Aerial attacks end as soon as the attacker lands.
+    pub fn is_aerial(self) -> bool {
+        matches!(self, AttackKind::JumpKick | AttackKind::DiveKick)
+    }
+
+    /// Heavy hits knock the victim down and shake the camera.
+    pub fn is_heavy(self) -> bool {
+        matches!(
+            self,
+            AttackKind::Uppercut
+                | AttackKind::Lunge
+                | AttackKind::DiveKick
+                | AttackKind::Throw
+                | AttackKind::Toss
+        )
+    }
+
+    pub fn damage(self) -> f32 {
+        match self {
+            AttackKind::Punch => 8.0,
+            AttackKind::Hook => 10.0,
+            AttackKind::Uppercut => 14.0,
+            AttackKind::Lunge => 14.0,
+            AttackKind::JumpKick => 10.0,
+            AttackKind::DiveKick => 15.0,
+            AttackKind::Throw => 12.0,
+            AttackKind::Toss => 15.0,
+        }
+    }
+
+    /// How far in front of the attacker the attack connects, in world units.
+    pub fn reach(self) -> f32 {
+        match self {
+            AttackKind::Punch => 180.0,
+            AttackKind::Hook => 180.0,
+            AttackKind::Uppercut => 170.0,
+            AttackKind::Lunge => 200.0,
+            AttackKind::JumpKick => 200.0,
+            AttackKind::DiveKick => 160.0,
+            // Throws don't need to reach, they always hit the grabbed character.
+            // Neither do tosses, the tossed item hits instead.
+            AttackKind::Throw | AttackKind::Toss => 0.0,
+        }
+    }
+
+    /// Initial speed the victim is pushed away with, in world units per second.
+    pub fn knockback(self) -> f32 {
+        match self {
+            AttackKind::Punch => 300.0,
+            AttackKind::Hook => 350.0,
+            AttackKind::Uppercut => 700.0,
+            AttackKind::Lunge => 750.0,
+            AttackKind::JumpKick => 500.0,
+            AttackKind::DiveKick => 800.0,
+            AttackKind::Throw => 900.0,
+            AttackKind::Toss => 600.0,
+        }
+    }
+
+    /// How long the attack lasts in total.
+    pub fn duration(self) -> Duration {
+        match self {
+            AttackKind::Punch => Duration::from_millis(300),
+            AttackKind::Hook => Duration::from_millis(300),
+            AttackKind::Uppercut => Duration::from_millis(450),
+            AttackKind::Lunge => Duration::from_millis(500),
+            AttackKind::JumpKick => Duration::from_millis(500),
+            // Ends when landing.
+            AttackKind::DiveKick => Duration::from_secs(1),
+            AttackKind::Throw => Duration::from_millis(400),
+            AttackKind::Toss => Duration::from_millis(300),
+        }
+    }
+
+    /// The part of the attack during which it can hit, as start and end time.
+    pub fn active(self) -> (Duration, Duration) {
+        match self {
+            AttackKind::Punch | AttackKind::Hook => {
+                (Duration::from_millis(50), Duration::from_millis(150))
+            }
+            AttackKind::Uppercut => (Duration::from_millis(100), Duration::from_millis(250)),
+            AttackKind::Lunge => (Duration::from_millis(100), Duration::from_millis(350)),
+            AttackKind::JumpKick => (Duration::from_millis(50), Duration::from_millis(400)),
+            AttackKind::DiveKick => (Duration::ZERO, Duration::from_secs(1)),
+            AttackKind::Throw | AttackKind::Toss => (Duration::ZERO, Duration::ZERO),
+        }
+    }
+
+    /// Whether the attack can hit `elapsed` after it started.
+    pub fn is_active(self, elapsed: Duration) -> bool {
+        let (start, end) = self.active();
+        (start..=end).contains(&elapsed)
+    }
+}
+
+/// Downward speed of a [`AttackKind::DiveKick`] in world units per second.
+const DIVE_KICK_SPEED: f32 = 1500.0;
+/// Forward speed of a [`AttackKind::Lunge`] in world units per second.
+const LUNGE_SPEED: f32 = 1400.0;
+/// How far apart on the ground plane two characters can be and still hit each other.
+pub const DEPTH_TOLERANCE: f32 = 60.0;
+/// How much of an attack's damage gets through a block.
+const BLOCK_DAMAGE_FACTOR: f32 = 0.2;
+/// How much of an attack's knockback pushes a blocking character back,
+/// and how much of it the attacker recoils with.
+const BLOCK_PUSHBACK_FACTOR: f32 = 0.5;
+/// How far apart in elevation two characters can be and still hit each other.
+const ELEVATION_TOLERANCE: f32 = 160.0;
+
+/// Everyone already hit by the current attack, so nobody is hit twice.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct HitVictims(Vec<Entity>);
+
+impl MapEntities for HitVictims {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for victim in &mut self.0 {
+            *victim = entity_mapper.map_entity(*victim);
+        }
+    }
+}
+
+/// Triggered when an attack connects.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Hit {
+    pub attacker: Entity,
+    pub victim: Entity,
+    pub kind: AttackKind,
+    /// The horizontal direction the victim is pushed in, `1.0` or `-1.0`.
+    pub direction: f32,
+}
+
+/// Triggered after a [`Hit`] has been turned into damage.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HitLanded {
+    pub attacker: Entity,
+    pub kind: AttackKind,
+    /// Whether the victim blocked the hit.
+    pub blocked: bool,
+    /// Whether the hit took the victim's last health.
+    pub knockout: bool,
+}
+
+/// Pushes a character over the ground after being hit.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect, Deref, DerefMut)]
+#[reflect(Component)]
+pub struct Knockback(pub Vec2);
+
+impl Knockback {
+    /// How quickly knockback wears off, in 1/seconds.
+    const FRICTION: f32 = 8.0;
+}
+
+/// Prepare an attack when entering [`CharacterState::Attack`].
+fn start_attack(
+    trigger: Trigger<StateEntered>,
+    mut commands: Commands,
+    mut airborne_query: Query<&mut Airborne>,
+    sprite_query: Query<&Sprite>,
+) {
+    let CharacterState::Attack(kind) = trigger.event().0 else {
+        return;
+    };
+    let entity = trigger.entity();
+    commands.entity(entity).insert(HitVictims::default());
+
+    if kind == AttackKind::DiveKick {
+        if let Ok(mut airborne) = airborne_query.get_mut(entity) {
+            airborne.velocity = -DIVE_KICK_SPEED;
+        }
+    }
+    if kind == AttackKind::Lunge {
+        if let Ok(sprite) = sprite_query.get(entity) {
+            let direction = if sprite.flip_x { -1.0 } else { 1.0 };
+            commands
+                .entity(entity)
+                .insert(Knockback(Vec2::X * direction * LUNGE_SPEED));
+        }
+    }
+}
+
+fn detect_hits(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    mut attacker_query: Query<(
+        Entity,
+        &StateMachine,
+        &Transform,
+        &Elevation,
+        &Sprite,
+        &mut HitVictims,
+        Has<Enemy>,
+    )>,
+    victim_query: Query<(Entity, &StateMachine, &Transform, &Elevation, Has<Enemy>), With<Health>>,
+) {
+    for (attacker, machine, transform, elevation, sprite, mut victims, enemy) in &mut attacker_query
+    {
+        let CharacterState::Attack(kind) = machine.state() else {
+            continue;
+        };
+        if !kind.is_active(machine.elapsed()) {
+            continue;
+        }
+        let direction = if sprite.flip_x { -1.0 } else { 1.0 };
+        let origin = transform.translation.xy();
+
+        for (victim, victim_machine, victim_transform, victim_elevation, victim_enemy) in
+            &victim_query
+        {
+            // Grunts don't hit each other, and neither do players fighting them together.
+            let friendly = enemy == victim_enemy && (enemy || *mode == GameMode::Waves);
+            if victim == attacker
+                || friendly
+                || victims.0.contains(&victim)
+                || !victim_machine.state().is_vulnerable()
+            {
+                continue;
+            }
+            let offset = victim_transform.translation.xy() - origin;
+            let in_front = (0.0..=kind.reach()).contains(&(offset.x * direction));
+            let same_depth = offset.y.abs() <= DEPTH_TOLERANCE;
+            let same_height = (victim_elevation.0 - elevation.0).abs() <= ELEVATION_TOLERANCE;
+            if in_front && same_depth && same_height {
+                victims.0.push(victim);
+                commands.trigger(Hit {
+                    attacker,
+                    victim,
+                    kind,
+                    direction,
+                });
+            }
+        }
+    }
+}
+
+fn apply_hit(
+    trigger: Trigger<Hit>,
+    mut commands: Commands,
+    mut victim_query: Query<(&mut Health, &mut Sprite, &mut StateMachine)>,
+    transform_query: Query<&Transform>,
+) {
+    let hit = *trigger.event();
+    let Ok((mut health, mut sprite, mut machine)) = victim_query.get_mut(hit.victim) else {
+        return;
+    };
+
+    // Blocking only works against attacks from the front.
+    // Victims are pushed away from the attacker, so they face the opposite way.
+    let facing_attacker = sprite.flip_x == (hit.direction > 0.0);
+    let blocked = machine.state() == CharacterState::Block && facing_attacker;
+
+    let knockback = Vec2::X * hit.direction * hit.kind.knockback();
+    if blocked {
+        health.current = (health.current - hit.kind.damage() * BLOCK_DAMAGE_FACTOR).max(0.0);
+        commands
+            .entity(hit.victim)
+            .insert(Knockback(knockback * BLOCK_PUSHBACK_FACTOR));
+        commands
+            .entity(hit.attacker)
+            .insert(Knockback(-knockback * BLOCK_PUSHBACK_FACTOR));
+        commands.trigger(Sfx::Block);
+        commands.trigger(HitLanded {
+            attacker: hit.attacker,
+            kind: hit.kind,
+            blocked: true,
+            knockout: false,
+        });
+        return;
+    }
+
+    health.current = (health.current - hit.kind.damage()).max(0.0);
+    let knockout = health.current <= 0.0;
+    machine.transition(if knockout || hit.kind.is_heavy() {
+        CharacterState::KnockedDown
+    } else {
+        CharacterState::HitStun
+    });
+
+    // Turn around to face the attacker.
+    if let Ok([attacker, victim]) = transform_query.get_many([hit.attacker, hit.victim]) {
+        sprite.flip_x = attacker.translation.x < victim.translation.x;
+    }
+
+    commands.entity(hit.victim).insert(Knockback(knockback));
+    commands.trigger(Sfx::Hit);
+    if hit.kind.is_heavy() {
+        commands.trigger(ShakeCamera { trauma: 0.5 });
+    }
+    commands.trigger(HitLanded {
+        attacker: hit.attacker,
+        kind: hit.kind,
+        blocked: false,
+        knockout,
+    });
+}
+
+fn apply_knockback(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut knockback_query: Query<(Entity, &mut Knockback, &mut Transform)>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, mut knockback, mut transform) in &mut knockback_query {
+        transform.translation += knockback.0.extend(0.0) * dt;
+        knockback.0 *= (-Knockback::FRICTION * dt).exp();
+        if knockback.0.length_squared() < 1.0 {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
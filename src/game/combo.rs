@@ -0,0 +1,232 @@
+//! Combo chains and input buffering.
+//! Every character remembers its recent directional and attack presses in an [`InputBuffer`].
+//! The [`MoveList`] decides which attack those inputs turn into, and which attacks can be
+//! cancelled into which others while the first one is still playing.
+
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+
+use super::{action::ActionController, combat::AttackKind, movement::MovementController};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(InputBuffer, MoveList)>();
+    app.init_resource::<MoveList>();
+    app.add_systems(FixedUpdate, buffer_input.in_set(AppSet::BufferInput));
+}
+
+/// A single press worth remembering for combos.
+/// Horizontal directions are relative to where the character is facing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ComboInput {
+    Up,
+    Down,
+    Forward,
+    Back,
+    Attack,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct BufferedInput {
+    pub input: ComboInput,
+    /// When the input was pressed, as elapsed app time.
+    pub time: Duration,
+}
+
+/// A character's recent presses, oldest first.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct InputBuffer {
+    inputs: VecDeque<BufferedInput>,
+    /// The directions held last frame, so that only new presses are buffered.
+    held: IVec2,
+}
+
+impl InputBuffer {
+    /// How long inputs are remembered.
+    const LIFETIME: Duration = Duration::from_millis(500);
+    /// How long a motion may pause between two of its steps.
+    const MAX_GAP: Duration = Duration::from_millis(200);
+
+    pub fn push(&mut self, input: ComboInput, time: Duration) {
+        self.inputs.push_back(BufferedInput { input, time });
+    }
+
+    /// Forget inputs that are too old to count towards a move.
+    pub fn expire(&mut self, now: Duration) {
+        while self
+            .inputs
+            .front()
+            .is_some_and(|buffered| now.saturating_sub(buffered.time) > Self::LIFETIME)
+        {
+            self.inputs.pop_front();
+        }
+    }
+
+    /// Whether an attack was pressed that has not been used up yet.
+    pub fn attack_pending(&self) -> bool {
+        self.inputs
+            .iter()
+            .any(|buffered| buffered.input == ComboInput::Attack)
+    }
+
+    /// Whether the buffer contains `motion` as consecutive presses,
+    /// each following the one before within [`Self::MAX_GAP`].
+    pub fn matches(&self, motion: &[ComboInput]) -> bool {
+        if self.inputs.len() < motion.len() {
+            return false;
+        }
+        (0..=self.inputs.len() - motion.len()).any(|start| {
+            let steps = self.inputs.range(start..start + motion.len());
+            steps
+                .clone()
+                .zip(motion)
+                .all(|(buffered, input)| buffered.input == *input)
+                && steps.clone().zip(steps.skip(1)).all(|(earlier, later)| {
+                    later.time.saturating_sub(earlier.time) <= Self::MAX_GAP
+                })
+        })
+    }
+
+    /// Use up all buffered inputs after performing a move.
+    pub fn clear(&mut self) {
+        self.inputs.clear();
+    }
+}
+
+/// An attack and the inputs that perform it.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct ComboMove {
+    pub kind: AttackKind,
+    /// The attack this move chains from, or `None` for moves that start a combo.
+    pub from: Option<AttackKind>,
+    /// The inputs to press in order, ending with the attack.
+    pub motion: Vec<ComboInput>,
+    /// When during `from` this move can cancel it, as start and end time.
+    pub cancel_window: (Duration, Duration),
+}
+
+/// Every grounded move, in order of priority.
+/// Aerial attacks don't chain and are chosen by [`AttackKind::choose`] instead.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct MoveList(pub Vec<ComboMove>);
+
+impl Default for MoveList {
+    fn default() -> Self {
+        use ComboInput::*;
+        let cancel_window = (Duration::from_millis(100), Duration::from_millis(300));
+        Self(vec![
+            ComboMove {
+                kind: AttackKind::Lunge,
+                from: None,
+                motion: vec![Down, Forward, Attack],
+                cancel_window: (Duration::ZERO, Duration::ZERO),
+            },
+            ComboMove {
+                kind: AttackKind::Punch,
+                from: None,
+                motion: vec![Attack],
+                cancel_window: (Duration::ZERO, Duration::ZERO),
+            },
+            ComboMove {
+                kind: AttackKind::Lunge,
+                from: Some(AttackKind::Punch),
+                motion: vec![Down, Forward, Attack],
+                cancel_window,
+            },
+            ComboMove {
+                kind: AttackKind::Hook,
+                from: Some(AttackKind::Punch),
+                motion: vec![Attack],
+                cancel_window,
+            },
+            ComboMove {
+                kind: AttackKind::Uppercut,
+                from: Some(AttackKind::Hook),
+                motion: vec![Attack],
+                cancel_window,
+            },
+        ])
+    }
+}
+
+impl MoveList {
+    /// The move that starts a combo from the buffered inputs, if any.
+    pub fn start(&self, buffer: &InputBuffer) -> Option<AttackKind> {
+        self.0
+            .iter()
+            .find(|combo_move| combo_move.from.is_none() && buffer.matches(&combo_move.motion))
+            .map(|combo_move| combo_move.kind)
+    }
+
+    /// The move that cancels `current` from the buffered inputs, if any,
+    /// given that `current` has been going on for `elapsed`.
+    pub fn chain(
+        &self,
+        buffer: &InputBuffer,
+        current: AttackKind,
+        elapsed: Duration,
+    ) -> Option<AttackKind> {
+        self.0
+            .iter()
+            .find(|combo_move| {
+                let (start, end) = combo_move.cancel_window;
+                combo_move.from == Some(current)
+                    && (start..=end).contains(&elapsed)
+                    && buffer.matches(&combo_move.motion)
+            })
+            .map(|combo_move| combo_move.kind)
+    }
+}
+
+fn buffer_input(
+    time: Res<Time>,
+    mut buffer_query: Query<(
+        &MovementController,
+        &ActionController,
+        &Sprite,
+        &mut InputBuffer,
+    )>,
+) {
+    let now = time.elapsed();
+    for (movement, action, sprite, mut buffer) in &mut buffer_query {
+        buffer.expire(now);
+
+        let facing = if sprite.flip_x { -1 } else { 1 };
+        let held = IVec2::new(axis(movement.0.x) * facing, axis(movement.0.y));
+        if held.x != 0 && held.x != buffer.held.x {
+            let input = if held.x > 0 {
+                ComboInput::Forward
+            } else {
+                ComboInput::Back
+            };
+            buffer.push(input, now);
+        }
+        if held.y != 0 && held.y != buffer.held.y {
+            let input = if held.y > 0 {
+                ComboInput::Up
+            } else {
+                ComboInput::Down
+            };
+            buffer.push(input, now);
+        }
+        buffer.held = held;
+
+        if action.attack {
+            buffer.push(ComboInput::Attack, now);
+        }
+    }
+}
+
+/// Which way along an axis a direction points: `-1`, `0` or `1`.
+fn axis(value: f32) -> i32 {
+    if value > 0.0 {
+        1
+    } else if value < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
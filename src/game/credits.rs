@@ -0,0 +1,87 @@
+//! Who made the game and its assets, read from `assets/game.credits.ron`.
+//! Attributions live in that file so they can be kept accurate as assets change.
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use serde::Deserialize;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Credits>();
+    app.register_asset_loader(CreditsLoader);
+}
+
+const CREDITS_PATH: &str = "game.credits.ron";
+
+/// The credits, shown as a roll on the credits screen.
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct Credits {
+    pub sections: Vec<CreditsSection>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreditsSection {
+    /// Looked up in the string tables, and shown as written if no table has it.
+    pub heading: String,
+    pub entries: Vec<CreditsEntry>,
+}
+
+/// A person or an asset.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreditsEntry {
+    pub name: String,
+    /// What the person did, or what the asset is used for.
+    #[serde(default)]
+    pub role: Option<String>,
+    /// The license, and whoever it requires to be credited.
+    #[serde(default)]
+    pub license: Option<String>,
+}
+
+impl CreditsEntry {
+    /// The entry's first line, like `"Alice - Programming"`.
+    pub fn title(&self) -> String {
+        match &self.role {
+            Some(role) => format!("{} - {role}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct CreditsLoader;
+
+impl AssetLoader for CreditsLoader {
+    type Asset = Credits;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Credits, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["credits.ron"]
+    }
+}
+
+#[derive(Resource, Debug, Deref)]
+pub struct CreditsHandle(Handle<Credits>);
+
+impl CreditsHandle {
+    pub fn new(asset_server: &AssetServer) -> Self {
+        Self(asset_server.load(CREDITS_PATH))
+    }
+
+    pub fn all_loaded(&self, assets: &Assets<Credits>) -> bool {
+        assets.contains(&self.0)
+    }
+}
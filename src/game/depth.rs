@@ -0,0 +1,75 @@
+//! Fake depth for a belt-scroller.
+//! Characters walk on a ground plane seen from the side, so things lower on screen
+//! are closer to the viewer and have to be drawn on top. Jumping lifts a sprite
+//! off the ground without changing its place on the plane, and thus its draw order.
+
+use bevy::{prelude::*, render::view::VisibilitySystems, transform::TransformSystem};
+
+use super::spawn::level::GroundPlane;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(YSort, Elevation)>();
+    app.add_systems(
+        PostUpdate,
+        (
+            (y_sort, refresh_elevated_transforms)
+                .chain()
+                .before(TransformSystem::TransformPropagate),
+            apply_elevation
+                .after(TransformSystem::TransformPropagate)
+                .before(VisibilitySystems::CheckVisibility),
+        ),
+    );
+}
+
+/// The z coordinate of things at the back of the [`GroundPlane`].
+/// Anything that should always be drawn behind characters, like the level itself, goes below this.
+pub const FAR_Z: f32 = 10.0;
+/// The z coordinate of things at the front of the [`GroundPlane`].
+/// Anything that should always be drawn in front of characters, like effects, goes above this.
+pub const NEAR_Z: f32 = 100.0;
+
+/// Sort a sprite by its position on the [`GroundPlane`].
+/// The entity's [`Transform`] is its position on the ground; its z coordinate is overwritten.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct YSort;
+
+/// How far above the ground an entity is, in world units.
+/// This only moves the sprite visually, its [`Transform`] stays on the ground.
+/// Children are not lifted, which is handy for shadows.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Reflect, Deref, DerefMut)]
+#[reflect(Component)]
+pub struct Elevation(pub f32);
+
+fn y_sort(ground: Res<GroundPlane>, mut sort_query: Query<&mut Transform, With<YSort>>) {
+    for mut transform in &mut sort_query {
+        let z = ground.depth(transform.translation.y);
+        if transform.translation.z != z {
+            transform.translation.z = z;
+        }
+    }
+}
+
+/// Transforms are only propagated when they change, so a new [`Elevation`] needs
+/// a fresh [`GlobalTransform`] for [`apply_elevation`] to lift.
+fn refresh_elevated_transforms(mut elevation_query: Query<&mut Transform, Changed<Elevation>>) {
+    for mut transform in &mut elevation_query {
+        transform.set_changed();
+    }
+}
+
+/// Lift sprites off the ground after their [`GlobalTransform`] has been computed from their ground position.
+/// Sprites whose [`GlobalTransform`] wasn't recomputed this frame have already been lifted.
+fn apply_elevation(
+    mut elevation_query: Query<(&Elevation, &mut GlobalTransform), Changed<GlobalTransform>>,
+) {
+    for (elevation, mut global_transform) in &mut elevation_query {
+        if elevation.0 == 0.0 {
+            continue;
+        }
+        let mut transform = global_transform.compute_transform();
+        transform.translation.y += elevation.0;
+        *global_transform = transform.into();
+    }
+}
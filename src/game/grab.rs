@@ -0,0 +1,225 @@
+//! Grabs and throws.
+//! Grabbing a character right in front of you holds on to them, which beats blocking.
+//! Attacking while holding on throws them in the held direction.
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+use super::{
+    action::ActionController,
+    audio::sfx::Sfx,
+    combat::{AttackKind, Hit, DEPTH_TOLERANCE},
+    jump::Airborne,
+    movement::MovementController,
+    spawn::enemy::Enemy,
+    state::{CharacterState, StateEntered, StateMachine},
+    waves::GameMode,
+};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Grabbing, GrabbedBy)>();
+    app.add_systems(
+        FixedUpdate,
+        (
+            try_grab.in_set(AppSet::ChangeState),
+            hold_grabbed.in_set(AppSet::Update),
+        ),
+    );
+    app.observe(throw);
+}
+
+/// How far in front of the grabber a character can be grabbed, in world units.
+const GRAB_RANGE: f32 = 150.0;
+/// How far in front of the grabber the grabbed character is held, in world units.
+const HOLD_DISTANCE: f32 = 110.0;
+/// Upward speed thrown characters are launched with, in world units per second.
+const THROW_LAUNCH_SPEED: f32 = 900.0;
+
+/// The character being held by this one.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct Grabbing(pub Entity);
+
+impl MapEntities for Grabbing {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+/// The character holding on to this one.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct GrabbedBy(pub Entity);
+
+impl MapEntities for GrabbedBy {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+fn try_grab(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    mut grab_query: Query<(
+        Entity,
+        &ActionController,
+        &Transform,
+        &Sprite,
+        Has<Airborne>,
+        &mut StateMachine,
+        Has<Enemy>,
+    )>,
+) {
+    let mut grabs = Vec::new();
+    for (grabber, action, transform, sprite, airborne, machine, enemy) in &grab_query {
+        if !action.grab || airborne || !machine.state().can_act() {
+            continue;
+        }
+        let direction = if sprite.flip_x { -1.0 } else { 1.0 };
+        let origin = transform.translation.xy();
+
+        // Grab the closest character in front of us.
+        let victim = grab_query
+            .iter()
+            .filter(|(victim, _, _, _, airborne, machine, victim_enemy)| {
+                // Only opponents can be grabbed, the same as only they can be hit.
+                let friendly = enemy == *victim_enemy && (enemy || *mode == GameMode::Waves);
+                *victim != grabber
+                    && !friendly
+                    && !airborne
+                    && machine.state().can_transition_to(CharacterState::Grabbed)
+            })
+            .map(|(victim, _, transform, ..)| (victim, transform.translation.xy() - origin))
+            .filter(|(_, offset)| {
+                (0.0..=GRAB_RANGE).contains(&(offset.x * direction))
+                    && offset.y.abs() <= DEPTH_TOLERANCE
+            })
+            .min_by(|(_, a), (_, b)| a.x.abs().total_cmp(&b.x.abs()));
+        if let Some((victim, _)) = victim {
+            grabs.push((grabber, victim));
+        }
+    }
+
+    for (grabber, victim) in grabs {
+        let Ok([mut grabber_item, mut victim_item]) = grab_query.get_many_mut([grabber, victim])
+        else {
+            continue;
+        };
+        // Check again, as an earlier grab this frame may have changed things.
+        if !grabber_item.5.state().can_act()
+            || !victim_item
+                .5
+                .state()
+                .can_transition_to(CharacterState::Grabbed)
+        {
+            continue;
+        }
+        grabber_item.5.transition(CharacterState::Grab);
+        victim_item.5.transition(CharacterState::Grabbed);
+        commands.entity(grabber).insert(Grabbing(victim));
+        commands.entity(victim).insert(GrabbedBy(grabber));
+        commands.trigger(Sfx::Grab);
+    }
+}
+
+/// Keep grabbed characters in front of their grabber,
+/// and let go once either of them is no longer part of the grab.
+fn hold_grabbed(
+    mut commands: Commands,
+    mut grabber_query: Query<
+        (Entity, &Grabbing, &Transform, &Sprite, &mut StateMachine),
+        Without<GrabbedBy>,
+    >,
+    mut victim_query: Query<
+        (Entity, &GrabbedBy, &mut Transform, &mut StateMachine),
+        Without<Grabbing>,
+    >,
+) {
+    for (grabber, grabbing, transform, sprite, mut machine) in &mut grabber_query {
+        let victim = victim_query.get_mut(grabbing.0);
+        let holding = machine.state() == CharacterState::Grab
+            && victim
+                .as_ref()
+                .is_ok_and(|(_, grabbed_by, _, victim_machine)| {
+                    grabbed_by.0 == grabber && victim_machine.state() == CharacterState::Grabbed
+                });
+
+        match victim {
+            Ok((_, _, mut victim_transform, _)) if holding => {
+                let direction = if sprite.flip_x { -1.0 } else { 1.0 };
+                let held = transform.translation.xy() + Vec2::X * direction * HOLD_DISTANCE;
+                victim_transform.translation = held.extend(victim_transform.translation.z);
+            }
+            victim => {
+                commands.entity(grabber).remove::<Grabbing>();
+                if machine.state() == CharacterState::Grab {
+                    machine.transition(CharacterState::Idle);
+                }
+                if let Ok((victim, _, _, mut victim_machine)) = victim {
+                    commands.entity(victim).remove::<GrabbedBy>();
+                    if victim_machine.state() == CharacterState::Grabbed {
+                        victim_machine.transition(CharacterState::Idle);
+                    }
+                }
+            }
+        }
+    }
+
+    // Let go of characters whose grabber is gone entirely.
+    for (victim, grabbed_by, _, mut machine) in &mut victim_query {
+        if grabber_query.contains(grabbed_by.0) {
+            continue;
+        }
+        commands.entity(victim).remove::<GrabbedBy>();
+        if machine.state() == CharacterState::Grabbed {
+            machine.transition(CharacterState::Idle);
+        }
+    }
+}
+
+/// Launch the grabbed character when entering [`CharacterState::Throw`].
+fn throw(
+    trigger: Trigger<StateEntered>,
+    mut commands: Commands,
+    mut grabber_query: Query<(&Grabbing, &MovementController, &mut Sprite)>,
+) {
+    if trigger.event().0 != CharacterState::Throw {
+        return;
+    }
+    let grabber = trigger.entity();
+    let Ok((grabbing, movement, mut sprite)) = grabber_query.get_mut(grabber) else {
+        return;
+    };
+    let victim = grabbing.0;
+
+    // Throw in the held direction, or forwards if none is held.
+    let direction = if movement.0.x != 0.0 {
+        movement.0.x.signum()
+    } else if sprite.flip_x {
+        -1.0
+    } else {
+        1.0
+    };
+    sprite.flip_x = direction < 0.0;
+
+    commands.entity(grabber).remove::<Grabbing>();
+    commands
+        .entity(victim)
+        .remove::<GrabbedBy>()
+        .insert(Airborne {
+            velocity: THROW_LAUNCH_SPEED,
+        });
+    commands.trigger(Sfx::Throw);
+    commands.trigger(Hit {
+        attacker: grabber,
+        victim,
+        kind: AttackKind::Throw,
+        direction,
+    });
+}
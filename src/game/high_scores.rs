@@ -0,0 +1,100 @@
+//! The high score table.
+//! It keeps the best [`HighScores::MAX_ENTRIES`] scores across sessions,
+//! loading them at startup and saving them whenever they change.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{spawn::player::Player, storage::Storage};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<HighScores>();
+    let high_scores = HighScores::load(app.world().resource::<Storage>());
+    app.insert_resource(high_scores);
+    app.add_systems(
+        Update,
+        save_high_scores
+            .run_if(resource_changed::<HighScores>.and_then(not(resource_added::<HighScores>))),
+    );
+}
+
+/// The best scores so far, highest first.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct HighScores {
+    pub entries: Vec<HighScoreEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    /// The initials the player entered.
+    pub name: String,
+    pub character: String,
+    pub score: u32,
+    /// When the score was set, as `YYYY-MM-DD`.
+    pub date: String,
+}
+
+impl HighScoreEntry {
+    /// An entry for a score set today.
+    pub fn new(name: impl Into<String>, player: Player, score: u32) -> Self {
+        Self {
+            name: name.into(),
+            character: player.character().to_string(),
+            score,
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+impl HighScores {
+    pub const MAX_ENTRIES: usize = 10;
+    const STORAGE_KEY: &'static str = "high_scores";
+
+    /// Whether `score` would make it onto the table.
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < Self::MAX_ENTRIES
+                || self.entries.last().is_some_and(|entry| score > entry.score))
+    }
+
+    /// Add an entry in its place, dropping the lowest one if the table is full.
+    /// Returns where the entry ended up, if it made it onto the table.
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        // Earlier entries win ties.
+        let rank = self
+            .entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(Self::MAX_ENTRIES);
+        Some(rank)
+    }
+
+    fn load(storage: &Storage) -> Self {
+        let Some(data) = storage.read(Self::STORAGE_KEY) else {
+            return Self::default();
+        };
+        ron::from_str(&data).unwrap_or_else(|error| {
+            warn!("Discarding unreadable high scores: {error}");
+            Self::default()
+        })
+    }
+}
+
+fn save_high_scores(high_scores: Res<HighScores>, mut storage: ResMut<Storage>) {
+    let data = match ron::ser::to_string_pretty(&*high_scores, default()) {
+        Ok(data) => data,
+        Err(error) => {
+            error!("Failed to serialize high scores: {error}");
+            return;
+        }
+    };
+    if let Err(error) = storage.write(HighScores::STORAGE_KEY, &data) {
+        error!("Failed to save high scores: {error}");
+    }
+}
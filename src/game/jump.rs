@@ -0,0 +1,113 @@
+//! Jumping, gravity and landing.
+//! Characters jump along the [`Elevation`] axis, so they stay on the same spot of the ground plane
+//! while in the air and keep their draw order.
+
+use bevy::prelude::*;
+
+use super::{
+    depth::Elevation,
+    state::{CharacterState, StateEntered},
+};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Gravity, Jump, Airborne, Shadow)>();
+    app.init_resource::<Gravity>();
+    app.add_systems(
+        FixedUpdate,
+        (apply_gravity, update_shadow)
+            .chain()
+            .in_set(AppSet::Update),
+    );
+    app.observe(start_jump);
+}
+
+/// Downward acceleration of airborne characters in world units per second squared.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect, Deref, DerefMut)]
+#[reflect(Resource)]
+pub struct Gravity(pub f32);
+
+impl Default for Gravity {
+    fn default() -> Self {
+        Self(3000.0)
+    }
+}
+
+/// Lets a character jump.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Jump {
+    /// Upward speed at takeoff in world units per second.
+    pub speed: f32,
+}
+
+/// Present while a character is in the air.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Airborne {
+    /// Vertical speed in world units per second, positive is up.
+    pub velocity: f32,
+}
+
+/// Triggered on a character when it touches the ground again.
+#[derive(Event, Debug)]
+pub struct Landed;
+
+/// A shadow on the ground below its parent.
+/// It shrinks the higher its parent is in the air.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Shadow {
+    /// The elevation at which the shadow has shrunk to half its size.
+    pub half_size_elevation: f32,
+}
+
+/// Take off when entering [`CharacterState::Jump`] from the ground.
+fn start_jump(
+    trigger: Trigger<StateEntered>,
+    mut commands: Commands,
+    jump_query: Query<&Jump, Without<Airborne>>,
+) {
+    if trigger.event().0 != CharacterState::Jump {
+        return;
+    }
+    let entity = trigger.entity();
+    if let Ok(jump) = jump_query.get(entity) {
+        commands.entity(entity).insert(Airborne {
+            velocity: jump.speed,
+        });
+    }
+}
+
+fn apply_gravity(
+    mut commands: Commands,
+    time: Res<Time>,
+    gravity: Res<Gravity>,
+    mut airborne_query: Query<(Entity, &mut Airborne, &mut Elevation)>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, mut airborne, mut elevation) in &mut airborne_query {
+        elevation.0 += airborne.velocity * dt;
+        airborne.velocity -= gravity.0 * dt;
+
+        if elevation.0 <= 0.0 && airborne.velocity <= 0.0 {
+            elevation.0 = 0.0;
+            commands.entity(entity).remove::<Airborne>();
+            commands.trigger_targets(Landed, entity);
+        }
+    }
+}
+
+fn update_shadow(
+    elevation_query: Query<&Elevation>,
+    mut shadow_query: Query<(&Shadow, &Parent, &mut Transform)>,
+) {
+    for (shadow, parent, mut transform) in &mut shadow_query {
+        let Ok(elevation) = elevation_query.get(parent.get()) else {
+            continue;
+        };
+        let scale =
+            shadow.half_size_elevation / (shadow.half_size_elevation + elevation.0.max(0.0));
+        transform.scale = Vec3::new(scale, scale, 1.0);
+    }
+}
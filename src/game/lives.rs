@@ -0,0 +1,45 @@
+//! Extra lives.
+//! A character with [`Lives`] left gets back up some time after dying, at full health.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    combat::{Health, Knockback},
+    jump::Airborne,
+    state::{CharacterState, StateMachine},
+};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Lives>();
+    app.add_systems(FixedUpdate, respawn.in_set(AppSet::ChangeState));
+}
+
+/// How many more times a character can come back after dying.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect, Deref, DerefMut)]
+#[reflect(Component)]
+pub struct Lives(pub u32);
+
+/// How long a character stays down before using up a life.
+const RESPAWN_DELAY: Duration = Duration::from_secs(2);
+
+fn respawn(
+    mut commands: Commands,
+    mut lives_query: Query<(Entity, &mut Lives, &mut Health, &mut StateMachine)>,
+) {
+    for (entity, mut lives, mut health, mut machine) in &mut lives_query {
+        if machine.state() != CharacterState::Dead
+            || machine.elapsed() < RESPAWN_DELAY
+            || lives.0 == 0
+        {
+            continue;
+        }
+        lives.0 -= 1;
+        health.current = health.max;
+        // Nothing leads out of `Dead`, so start over with a fresh state machine.
+        *machine = StateMachine::default();
+        commands.entity(entity).remove::<(Knockback, Airborne)>();
+    }
+}
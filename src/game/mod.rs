@@ -2,17 +2,73 @@
 
 use bevy::prelude::*;
 
+mod action;
+pub mod ai;
 mod animation;
 pub mod assets;
 pub mod audio;
-mod movement;
+mod camera;
+pub mod combat;
+mod combo;
+pub mod credits;
+mod depth;
+mod grab;
+pub mod high_scores;
+mod jump;
+mod lives;
+pub mod movement;
+pub mod net;
+mod pickup;
+pub mod replay;
+mod rng;
+pub mod round;
+pub mod save;
+pub mod score;
+pub mod settings;
 pub mod spawn;
+pub mod state;
+pub mod storage;
+pub mod waves;
 
-pub(super) fn plugin(app: &mut App) {
+/// The rules of the game. These run without a window, audio or rendering.
+pub(super) fn simulation_plugin(app: &mut App) {
+    app.add_plugins((
+        (
+            action::plugin,
+            ai::plugin,
+            combat::plugin,
+            combo::plugin,
+            depth::plugin,
+            grab::plugin,
+            jump::plugin,
+            lives::plugin,
+        ),
+        (
+            movement::plugin,
+            net::plugin,
+            pickup::plugin,
+            replay::plugin,
+            rng::plugin,
+            round::plugin,
+            save::plugin,
+            score::plugin,
+            spawn::plugin,
+            state::plugin,
+            storage::plugin,
+            waves::plugin,
+        ),
+    ));
+}
+
+/// How the game looks and sounds, and what is kept between matches.
+pub(super) fn presentation_plugin(app: &mut App) {
     app.add_plugins((
         animation::plugin,
         audio::plugin,
-        movement::plugin,
-        spawn::plugin,
+        camera::plugin,
+        credits::plugin,
+        high_scores::plugin,
+        settings::plugin,
+        spawn::presentation_plugin,
     ));
 }
@@ -1,25 +1,32 @@
 //! Handle player input and translate it into movement.
-//! Note that the approach used here is simple for demonstration purposes.
-//! If you want to move the player in a smoother way,
-//! consider using a [fixed timestep](https://github.com/bevyengine/bevy/blob/latest/examples/movement/physics_in_fixed_timestep.rs).
+//! Input is read every frame, while movement is applied in the fixed simulation ticks.
 
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 
-use crate::AppSet;
+use crate::{
+    game::{
+        replay::ReplayPlayback,
+        spawn::level::{GroundPlane, LevelBounds},
+        state::{CharacterState, StateMachine},
+    },
+    AppSet,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Record directional input as movement controls.
-    app.register_type::<MovementController>();
+    app.register_type::<(MovementController, KeyBindings)>();
     app.add_systems(
         Update,
-        record_movement_controller.in_set(AppSet::RecordInput),
+        record_movement_controller
+            .in_set(AppSet::RecordInput)
+            .run_if(not(resource_exists::<ReplayPlayback>)),
     );
 
     // Apply movement based on controls.
-    app.register_type::<(Movement, WrapWithinWindow)>();
+    app.register_type::<(Movement, ConfineToLevel)>();
     app.add_systems(
-        Update,
-        (apply_movement, wrap_within_window)
+        FixedUpdate,
+        (apply_movement, confine_to_level)
             .chain()
             .in_set(AppSet::Update),
     );
@@ -29,32 +36,70 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Component)]
 pub struct MovementController(pub Vec2);
 
+/// The keys a player presses to move and act.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub run: KeyCode,
+    pub jump: KeyCode,
+    pub attack: KeyCode,
+    pub block: KeyCode,
+    pub grab: KeyCode,
+}
+
+impl KeyBindings {
+    pub const WASD: Self = Self {
+        up: KeyCode::KeyW,
+        down: KeyCode::KeyS,
+        left: KeyCode::KeyA,
+        right: KeyCode::KeyD,
+        run: KeyCode::ShiftLeft,
+        jump: KeyCode::Space,
+        attack: KeyCode::KeyF,
+        block: KeyCode::KeyG,
+        grab: KeyCode::KeyE,
+    };
+
+    pub const ARROWS: Self = Self {
+        up: KeyCode::ArrowUp,
+        down: KeyCode::ArrowDown,
+        left: KeyCode::ArrowLeft,
+        right: KeyCode::ArrowRight,
+        run: KeyCode::ShiftRight,
+        jump: KeyCode::Numpad0,
+        attack: KeyCode::Numpad1,
+        block: KeyCode::Numpad2,
+        grab: KeyCode::Numpad3,
+    };
+}
+
 fn record_movement_controller(
     input: Res<ButtonInput<KeyCode>>,
-    mut controller_query: Query<&mut MovementController>,
+    mut controller_query: Query<(&KeyBindings, &mut MovementController)>,
 ) {
-    // Collect directional input.
-    let mut intent = Vec2::ZERO;
-    if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
-        intent.y += 1.0;
-    }
-    if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
-        intent.y -= 1.0;
-    }
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
-        intent.x -= 1.0;
-    }
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
-        intent.x += 1.0;
-    }
-
-    // Normalize so that diagonal movement has the same speed as
-    // horizontal and vertical movement.
-    let intent = intent.normalize_or_zero();
+    for (bindings, mut controller) in &mut controller_query {
+        // Collect directional input.
+        let mut intent = Vec2::ZERO;
+        if input.pressed(bindings.up) {
+            intent.y += 1.0;
+        }
+        if input.pressed(bindings.down) {
+            intent.y -= 1.0;
+        }
+        if input.pressed(bindings.left) {
+            intent.x -= 1.0;
+        }
+        if input.pressed(bindings.right) {
+            intent.x += 1.0;
+        }
 
-    // Apply movement intent to controllers.
-    for mut controller in &mut controller_query {
-        controller.0 = intent;
+        // Normalize so that diagonal movement has the same speed as
+        // horizontal and vertical movement.
+        controller.0 = intent.normalize_or_zero();
     }
 }
 
@@ -68,29 +113,46 @@ pub struct Movement {
     pub speed: f32,
 }
 
+/// How much faster running is than walking.
+const RUN_SPEED_MULTIPLIER: f32 = 1.8;
+
 fn apply_movement(
     time: Res<Time>,
-    mut movement_query: Query<(&MovementController, &Movement, &mut Transform)>,
+    mut movement_query: Query<(
+        &MovementController,
+        &Movement,
+        Option<&StateMachine>,
+        &mut Transform,
+    )>,
 ) {
-    for (controller, movement, mut transform) in &mut movement_query {
-        let velocity = movement.speed * controller.0;
+    for (controller, movement, machine, mut transform) in &mut movement_query {
+        let state = machine.map_or(CharacterState::Walk, StateMachine::state);
+        if !state.can_act() {
+            continue;
+        }
+        let speed = if state == CharacterState::Run {
+            movement.speed * RUN_SPEED_MULTIPLIER
+        } else {
+            movement.speed
+        };
+        let velocity = speed * controller.0;
         transform.translation += velocity.extend(0.0) * time.delta_seconds();
     }
 }
 
+/// Keeps an entity inside the [`LevelBounds`] and on the [`GroundPlane`].
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-pub struct WrapWithinWindow;
+pub struct ConfineToLevel;
 
-fn wrap_within_window(
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    mut wrap_query: Query<&mut Transform, With<WrapWithinWindow>>,
+fn confine_to_level(
+    bounds: Res<LevelBounds>,
+    ground: Res<GroundPlane>,
+    mut confine_query: Query<&mut Transform, With<ConfineToLevel>>,
 ) {
-    let size = window_query.single().size() + 256.0;
-    let half_size = size / 2.0;
-    for mut transform in &mut wrap_query {
-        let position = transform.translation.xy();
-        let wrapped = (position + half_size).rem_euclid(size) - half_size;
-        transform.translation = wrapped.extend(transform.translation.z);
+    for mut transform in &mut confine_query {
+        let position = transform.translation.xy().clamp(bounds.min, bounds.max);
+        let position = position.with_y(ground.clamp(position.y));
+        transform.translation = position.extend(transform.translation.z);
     }
 }
@@ -0,0 +1,569 @@
+//! Networked versus matches.
+//! Both players' games simulate the whole match, and only exchange their inputs over UDP.
+//! Local input is delayed by a few ticks to give it time to arrive on the other side.
+//! Where the other player's input hasn't arrived yet, it is predicted to stay the same,
+//! and once it turns out different, the match is [rolled back](rollback) and simulated again.
+//!
+//! Players meet in the lobby screens, or by starting one game with `--host <port>` and another
+//! with `--join <address>`, e.g. `--host 7000` and `--join 127.0.0.1:7000` on one machine.
+//! The match starts once both players are ready.
+
+mod rollback;
+mod transport;
+
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use bevy::{ecs::world::Command, prelude::*, ui::Val::*};
+
+use self::{
+    rollback::{roll_back, take_snapshot, Snapshot},
+    transport::{Message, Transport},
+};
+use super::{
+    action::ActionController,
+    ai::{drive_ai, Opponent},
+    assets::{FontAsset, FontAssets},
+    movement::{KeyBindings, MovementController},
+    replay::TickInput,
+    spawn::player::Player,
+    waves::GameMode,
+};
+use crate::{
+    screen::Screen,
+    ui::localization::{Localized, LocalizedText},
+    AppSet,
+};
+
+pub use self::rollback::Resimulating;
+
+pub(super) fn plugin(app: &mut App) {
+    if let Some(connection) = connection_argument() {
+        app.insert_resource(connection);
+    }
+    app.add_systems(
+        PreUpdate,
+        (
+            receive_messages,
+            (roll_back, throttle)
+                .chain()
+                .run_if(resource_exists::<NetSession>),
+        )
+            .chain()
+            .run_if(resource_exists::<NetConnection>),
+    );
+    app.add_systems(Last, send_messages.run_if(resource_exists::<NetConnection>));
+
+    app.add_systems(
+        FixedPreUpdate,
+        take_snapshot.run_if(in_state(Screen::Playing).and_then(resource_exists::<NetSession>)),
+    );
+    app.add_systems(
+        FixedUpdate,
+        exchange_inputs
+            .in_set(AppSet::RecordInput)
+            .after(drive_ai)
+            .run_if(in_state(Screen::Playing).and_then(resource_exists::<NetSession>)),
+    );
+
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        spawn_latency_ui.run_if(resource_exists::<NetSession>),
+    );
+    app.add_systems(OnExit(Screen::Playing), end_session);
+    app.add_systems(
+        Update,
+        (
+            start_match.run_if(
+                in_state(Screen::Host)
+                    .or_else(in_state(Screen::Join))
+                    .and_then(not(resource_exists::<NetSession>)),
+            ),
+            (bind_local_player, update_latency_ui).run_if(resource_exists::<NetSession>),
+        )
+            .run_if(resource_exists::<NetConnection>),
+    );
+}
+
+/// How many ticks local input is delayed by.
+const INPUT_DELAY: u32 = 2;
+/// How many ticks ahead of the other player's confirmed input the match may run.
+/// Beyond that, the game waits for them.
+const MAX_PREDICTION: u32 = 8;
+/// How often to measure the latency, and to ask a host for a match.
+/// Also how often to tell the other player whether we're ready.
+const PING_INTERVAL: Duration = Duration::from_millis(500);
+/// How long the other player may go silent before they are considered gone.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetRole {
+    Host,
+    Join,
+}
+
+impl NetRole {
+    /// Which player this side controls.
+    fn player(self) -> Player {
+        match self {
+            NetRole::Host => Player::Player1,
+            NetRole::Join => Player::Player2,
+        }
+    }
+}
+
+/// The connection to the other player of a networked match.
+#[derive(Resource, Debug)]
+pub struct NetConnection {
+    transport: Transport,
+    role: NetRole,
+    /// Whether the host and the joining player found each other.
+    connected: bool,
+    ready: bool,
+    peer_ready: bool,
+    /// The seed of the match the host started, for the joining player to start it too.
+    start: Option<u64>,
+    /// When the other player was last heard from, in real time.
+    last_heard: Duration,
+    last_ping: Duration,
+    /// The round trip time to the other player.
+    latency: Option<Duration>,
+}
+
+impl NetConnection {
+    /// Wait for another player to join on `port`.
+    pub fn host(port: u16) -> std::io::Result<Self> {
+        let transport = Transport::bind((Ipv4Addr::UNSPECIFIED, port).into(), None)?;
+        Ok(Self::new(transport, NetRole::Host))
+    }
+
+    /// Ask the host at `address` for a match.
+    pub fn join(address: SocketAddr) -> std::io::Result<Self> {
+        let transport = Transport::bind((Ipv4Addr::UNSPECIFIED, 0).into(), Some(address))?;
+        Ok(Self::new(transport, NetRole::Join))
+    }
+
+    fn new(transport: Transport, role: NetRole) -> Self {
+        Self {
+            transport,
+            role,
+            connected: false,
+            ready: false,
+            peer_ready: false,
+            start: None,
+            last_heard: Duration::ZERO,
+            last_ping: Duration::ZERO,
+            latency: None,
+        }
+    }
+
+    pub fn role(&self) -> NetRole {
+        self.role
+    }
+
+    /// The port we receive on.
+    pub fn local_port(&self) -> Option<u16> {
+        self.transport.local_port()
+    }
+
+    /// The other player's address, once known.
+    pub fn peer(&self) -> Option<SocketAddr> {
+        self.transport.peer()
+    }
+
+    /// Whether the host and the joining player found each other.
+    pub fn connected(&self) -> bool {
+        self.connected
+    }
+
+    /// The round trip time to the other player, once measured.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
+    pub fn ready(&self) -> bool {
+        self.ready
+    }
+
+    pub fn peer_ready(&self) -> bool {
+        self.peer_ready
+    }
+
+    pub fn set_ready(&mut self, ready: bool) {
+        self.ready = ready;
+        self.transport.send(&Message::Ready(ready));
+    }
+}
+
+/// Triggered when the connection to the other player ends.
+#[derive(Event, Debug, Clone)]
+pub struct Disconnected {
+    pub role: NetRole,
+    pub reason: Localized,
+}
+
+/// Close the connection to the other player, leaving any match in progress.
+pub struct Disconnect {
+    pub reason: Localized,
+}
+
+impl Command for Disconnect {
+    fn apply(self, world: &mut World) {
+        let Some(connection) = world.remove_resource::<NetConnection>() else {
+            return;
+        };
+        connection.transport.send(&Message::Goodbye);
+        world.remove_resource::<NetSession>();
+        world.resource_mut::<Time<Virtual>>().unpause();
+        world.trigger(Disconnected {
+            role: connection.role,
+            reason: self.reason,
+        });
+        world.flush();
+    }
+}
+
+/// Read `--host <port>` or `--join <address>` from the command line.
+fn connection_argument() -> Option<NetConnection> {
+    let mut args = std::env::args();
+    let connection = loop {
+        match args.next()?.as_str() {
+            "--host" => {
+                let port = args.next()?;
+                break port
+                    .parse()
+                    .map_err(|error| format!("invalid port {port}: {error}"))
+                    .and_then(|port| NetConnection::host(port).map_err(|error| error.to_string()));
+            }
+            "--join" => {
+                let address = args.next()?;
+                break address
+                    .parse()
+                    .map_err(|error| format!("invalid address {address}: {error}"))
+                    .and_then(|address| {
+                        NetConnection::join(address).map_err(|error| error.to_string())
+                    });
+            }
+            _ => {}
+        }
+    };
+    connection
+        .map_err(|error| error!("Can't start a networked match: {error}"))
+        .ok()
+}
+
+/// The state of a networked match in progress.
+#[derive(Resource)]
+pub struct NetSession {
+    local: Player,
+    seed: u64,
+    /// The next tick to simulate.
+    tick: u32,
+    /// Our inputs for every tick, including the ones delayed into the future.
+    local_inputs: Vec<TickInput>,
+    /// The other player's inputs for every tick they were received for.
+    remote_inputs: Vec<TickInput>,
+    /// The other player's inputs the simulated ticks used, whether received or predicted.
+    used_inputs: Vec<TickInput>,
+    /// How many ticks of our inputs the other player received.
+    acknowledged: u32,
+    /// The earliest tick that has to be simulated again.
+    rollback: Option<u32>,
+    snapshots: Vec<Snapshot>,
+}
+
+impl NetSession {
+    fn new(local: Player, seed: u64) -> Self {
+        Self {
+            local,
+            seed,
+            tick: 0,
+            local_inputs: vec![TickInput::default(); INPUT_DELAY as usize],
+            remote_inputs: Vec::new(),
+            used_inputs: Vec::new(),
+            acknowledged: 0,
+            rollback: None,
+            snapshots: Vec::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// What the other player pressed during `tick`, or our best guess.
+    fn remote_input(&self, tick: u32) -> TickInput {
+        match self.remote_inputs.get(tick as usize) {
+            Some(input) => *input,
+            None => self
+                .remote_inputs
+                .last()
+                .map_or_else(TickInput::default, |input| input.held()),
+        }
+    }
+
+    fn receive(&mut self, start: u32, inputs: Vec<TickInput>, received: u32) {
+        self.acknowledged = self.acknowledged.max(received);
+        for (tick, input) in (start..).zip(inputs) {
+            if tick as usize != self.remote_inputs.len() {
+                continue;
+            }
+            self.remote_inputs.push(input);
+            let mispredicted = self
+                .used_inputs
+                .get(tick as usize)
+                .is_some_and(|used| *used != input);
+            if mispredicted {
+                self.rollback = Some(self.rollback.map_or(tick, |from| from.min(tick)));
+            }
+        }
+        // Ticks with confirmed inputs are never rolled back again.
+        let oldest = self
+            .rollback
+            .unwrap_or(self.remote_inputs.len() as u32)
+            .min(self.remote_inputs.len() as u32);
+        self.snapshots.retain(|snapshot| snapshot.tick >= oldest);
+    }
+}
+
+fn receive_messages(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut connection: ResMut<NetConnection>,
+    mut session: Option<ResMut<NetSession>>,
+) {
+    let now = time.elapsed();
+    for (sender, message) in connection.transport.receive() {
+        connection.last_heard = now;
+        match message {
+            Message::Hello => {
+                if connection.role != NetRole::Host {
+                    continue;
+                }
+                if !connection.connected {
+                    info!("{sender} joined");
+                    connection.transport.connect(sender);
+                    connection.connected = true;
+                }
+                // Answer every time, in case the welcome gets lost.
+                connection.transport.send(&Message::Welcome);
+            }
+            Message::Welcome => {
+                if connection.role == NetRole::Join && !connection.connected {
+                    info!("Joined {sender}");
+                    connection.connected = true;
+                }
+            }
+            Message::Ready(ready) => connection.peer_ready = ready,
+            Message::Start { seed } => {
+                // Only the first of the repeated starts counts.
+                if connection.role == NetRole::Join && connection.ready && session.is_none() {
+                    connection.start = Some(seed);
+                }
+            }
+            Message::Inputs {
+                start,
+                inputs,
+                received,
+            } => {
+                if let Some(session) = session.as_mut() {
+                    session.receive(start, inputs, received);
+                }
+            }
+            Message::Ping(sent) => connection.transport.send(&Message::Pong(sent)),
+            Message::Pong(sent) => {
+                connection.latency = Some(now.saturating_sub(Duration::from_secs_f64(sent)));
+            }
+            Message::Goodbye => {
+                info!("The other player left");
+                commands.add(Disconnect {
+                    reason: Localized::new("peer-left"),
+                });
+                return;
+            }
+        }
+    }
+
+    if connection.connected && now.saturating_sub(connection.last_heard) > TIMEOUT {
+        warn!("Lost the connection to the other player");
+        commands.add(Disconnect {
+            reason: Localized::new("connection-lost"),
+        });
+    }
+}
+
+fn send_messages(
+    time: Res<Time<Real>>,
+    mut connection: ResMut<NetConnection>,
+    session: Option<Res<NetSession>>,
+) {
+    let now = time.elapsed();
+    if now.saturating_sub(connection.last_ping) >= PING_INTERVAL {
+        connection.last_ping = now;
+        if connection.connected {
+            connection.transport.send(&Message::Ping(now.as_secs_f64()));
+            connection.transport.send(&Message::Ready(connection.ready));
+        } else if connection.role == NetRole::Join {
+            connection.transport.send(&Message::Hello);
+        }
+    }
+
+    let Some(session) = session else {
+        return;
+    };
+    // Until the joining player's inputs arrive, they may not know the match started.
+    if connection.role == NetRole::Host && session.remote_inputs.is_empty() {
+        connection
+            .transport
+            .send(&Message::Start { seed: session.seed });
+    }
+    // Resend everything the other player hasn't received yet, in case it got lost.
+    connection.transport.send(&Message::Inputs {
+        start: session.acknowledged,
+        inputs: session.local_inputs[session.acknowledged as usize..].to_vec(),
+        received: session.remote_inputs.len() as u32,
+    });
+}
+
+/// Start playing once both players are ready.
+/// The host picks the seed and tells the joining player to start as well.
+fn start_match(
+    mut commands: Commands,
+    mut connection: ResMut<NetConnection>,
+    mut mode: ResMut<GameMode>,
+    mut opponent: ResMut<Opponent>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let seed = match connection.role {
+        NetRole::Host if connection.connected && connection.ready && connection.peer_ready => {
+            rand::random()
+        }
+        NetRole::Host => return,
+        NetRole::Join => {
+            let Some(seed) = connection.start.take() else {
+                return;
+            };
+            seed
+        }
+    };
+    // Everyone has to ready up again for the next match.
+    connection.ready = false;
+    connection.peer_ready = false;
+
+    *mode = GameMode::Versus;
+    *opponent = Opponent::Human;
+    commands.insert_resource(NetSession::new(connection.role.player(), seed));
+    next_screen.set(Screen::Playing);
+}
+
+/// Control our player with the usual keys, and leave the other to the network.
+fn bind_local_player(
+    mut commands: Commands,
+    session: Res<NetSession>,
+    player_query: Query<(Entity, &Player), Added<Player>>,
+) {
+    for (entity, player) in &player_query {
+        if *player == session.local {
+            commands.entity(entity).insert(KeyBindings::WASD);
+        } else {
+            commands.entity(entity).remove::<KeyBindings>();
+        }
+    }
+}
+
+/// Feed both players' inputs for this tick into their controllers.
+fn exchange_inputs(
+    mut session: ResMut<NetSession>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut controller_query: Query<(&Player, &mut MovementController, &mut ActionController)>,
+) {
+    let tick = session.tick;
+    let local = session.local;
+
+    // Ticks simulated again already have their input.
+    if (tick + INPUT_DELAY) as usize == session.local_inputs.len() {
+        let input = controller_query
+            .iter()
+            .find(|(player, ..)| **player == local)
+            .map_or_else(TickInput::default, |(_, movement, action)| {
+                TickInput::new(movement, action)
+            });
+        session.local_inputs.push(input);
+    }
+
+    let local_input = session.local_inputs[tick as usize];
+    let remote_input = session.remote_input(tick);
+    session.used_inputs.truncate(tick as usize);
+    session.used_inputs.push(remote_input);
+    for (player, mut movement, mut action) in &mut controller_query {
+        let input = if *player == local {
+            local_input
+        } else {
+            remote_input
+        };
+        input.apply(&mut movement, &mut action);
+    }
+    session.tick += 1;
+
+    // Wait for the other player before running too far ahead.
+    if session.tick >= session.remote_inputs.len() as u32 + MAX_PREDICTION {
+        fixed_time.discard_overstep(Duration::MAX);
+    }
+}
+
+/// Hold the game while too far ahead of the other player.
+fn throttle(session: Res<NetSession>, mut time: ResMut<Time<Virtual>>) {
+    if session.tick >= session.remote_inputs.len() as u32 + MAX_PREDICTION {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+}
+
+fn end_session(mut commands: Commands, mut time: ResMut<Time<Virtual>>) {
+    commands.remove_resource::<NetSession>();
+    time.unpause();
+}
+
+#[derive(Component)]
+struct LatencyUi;
+
+fn spawn_latency_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands.spawn((
+        Name::new("Latency UI"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: fonts[&FontAsset::Pixel].clone_weak(),
+                font_size: 16.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Px(10.0),
+            right: Px(10.0),
+            ..default()
+        }),
+        LocalizedText::new(Localized::new("ping-unknown")),
+        LatencyUi,
+        StateScoped(Screen::Playing),
+    ));
+}
+
+fn update_latency_ui(
+    connection: Res<NetConnection>,
+    mut ui_query: Query<&mut LocalizedText, With<LatencyUi>>,
+) {
+    let latency = match connection.latency() {
+        Some(latency) => Localized::new("ping").with_arg("ms", latency.as_millis().to_string()),
+        None => Localized::new("ping-unknown"),
+    };
+    for mut text in &mut ui_query {
+        if text.text != latency {
+            text.text = latency.clone();
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Rolling the match back to fix mispredicted ticks.
+//! A snapshot of the match is taken before every tick. Once the other player's actual input
+//! for a tick turns out different from what was predicted, the snapshot from before that tick
+//! is restored and the ticks since are simulated again.
+//! That includes the end of the round, which may turn out to not have happened after all.
+
+use bevy::{app::FixedMain, ecs::world::Command, prelude::*};
+
+use super::{Disconnect, NetSession};
+use crate::{
+    game::{
+        rng::GameRng,
+        round::{RoundEnd, UndoRoundOver},
+        save,
+    },
+    ui::localization::Localized,
+};
+
+/// The state of the match before a tick.
+pub(super) struct Snapshot {
+    pub(super) tick: u32,
+    scene: DynamicScene,
+    rng: GameRng,
+    time: Time<Fixed>,
+    round_end: Option<RoundEnd>,
+}
+
+/// Present while ticks are simulated again, for effects that shouldn't play twice.
+#[derive(Resource, Debug)]
+pub struct Resimulating;
+
+pub(super) fn take_snapshot(world: &mut World) {
+    let tick = world.resource::<NetSession>().tick;
+    let snapshot = Snapshot {
+        tick,
+        scene: save::capture(world),
+        rng: world.resource::<GameRng>().clone(),
+        time: *world.resource::<Time<Fixed>>(),
+        round_end: world.get_resource::<RoundEnd>().cloned(),
+    };
+    let mut session = world.resource_mut::<NetSession>();
+    session.snapshots.retain(|snapshot| snapshot.tick < tick);
+    session.snapshots.push(snapshot);
+}
+
+pub(super) fn roll_back(world: &mut World) {
+    let mut session = world.resource_mut::<NetSession>();
+    let Some(from) = session.rollback.take() else {
+        return;
+    };
+    let until = session.tick;
+    let Some(index) = session
+        .snapshots
+        .iter()
+        .position(|snapshot| snapshot.tick == from)
+    else {
+        error!("Can't roll back to tick {from}, the match is out of sync");
+        world.commands().add(Disconnect {
+            reason: Localized::new("out-of-sync"),
+        });
+        return;
+    };
+    // Simulating the ticks again takes new snapshots.
+    let snapshot = session.snapshots.drain(index..).next().unwrap();
+    session.tick = from;
+
+    save::restore(world, &snapshot.scene);
+    match snapshot.round_end {
+        Some(round_end) => world.insert_resource(round_end),
+        None => UndoRoundOver.apply(world),
+    }
+    world.insert_resource(snapshot.rng);
+    let now = *world.resource::<Time<Fixed>>();
+    world.insert_resource(snapshot.time);
+    world.insert_resource(Resimulating);
+    for _ in from..until {
+        *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+        world.run_schedule(FixedMain);
+        let mut fixed_time = world.resource_mut::<Time<Fixed>>();
+        let timestep = fixed_time.timestep();
+        fixed_time.advance_by(timestep);
+    }
+    world.remove_resource::<Resimulating>();
+    world.insert_resource(now);
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
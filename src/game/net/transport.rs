@@ -0,0 +1,114 @@
+//! Sending messages to the other player over UDP.
+//! Messages may get lost or arrive out of order, so everything important is sent repeatedly.
+
+use std::{
+    io,
+    net::{SocketAddr, UdpSocket},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::replay::TickInput;
+
+/// Everything the two players tell each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) enum Message {
+    /// Asks the host for a match.
+    Hello,
+    /// Accepts a [`Message::Hello`].
+    Welcome,
+    /// Whether the sender is ready to play.
+    Ready(bool),
+    /// The host started a match with this seed.
+    Start { seed: u64 },
+    /// The sender's inputs from tick `start` on,
+    /// and how many ticks of inputs they received from us so far.
+    Inputs {
+        start: u32,
+        inputs: Vec<TickInput>,
+        received: u32,
+    },
+    /// Measures the round trip time. Holds the real time the ping was sent at, in seconds.
+    Ping(f64),
+    /// Answers a [`Message::Ping`] with its time.
+    Pong(f64),
+    /// The sender left.
+    Goodbye,
+}
+
+/// A non-blocking UDP socket talking to a single peer.
+#[derive(Debug)]
+pub(super) struct Transport {
+    socket: UdpSocket,
+    peer: Option<SocketAddr>,
+}
+
+impl Transport {
+    /// Big enough for any message we send.
+    const MAX_MESSAGE_SIZE: usize = 4096;
+
+    pub(super) fn bind(address: SocketAddr, peer: Option<SocketAddr>) -> io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket, peer })
+    }
+
+    pub(super) fn local_port(&self) -> Option<u16> {
+        self.socket.local_addr().ok().map(|address| address.port())
+    }
+
+    pub(super) fn peer(&self) -> Option<SocketAddr> {
+        self.peer
+    }
+
+    /// Only talk to `peer` from now on.
+    pub(super) fn connect(&mut self, peer: SocketAddr) {
+        self.peer = Some(peer);
+    }
+
+    pub(super) fn send(&self, message: &Message) {
+        let Some(peer) = self.peer else {
+            return;
+        };
+        let data = match ron::to_string(message) {
+            Ok(data) => data,
+            Err(error) => {
+                error!("Failed to serialize {message:?}: {error}");
+                return;
+            }
+        };
+        // Lost messages are sent again anyway.
+        if let Err(error) = self.socket.send_to(data.as_bytes(), peer) {
+            debug!("Failed to send to {peer}: {error}");
+        }
+    }
+
+    /// Everything that arrived since the last call, along with who sent it.
+    pub(super) fn receive(&self) -> Vec<(SocketAddr, Message)> {
+        let mut buffer = [0; Self::MAX_MESSAGE_SIZE];
+        let mut messages = Vec::new();
+        loop {
+            let (length, sender) = match self.socket.recv_from(&mut buffer) {
+                Ok(received) => received,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                // E.g. the peer's port being closed, which timing out covers.
+                Err(error) => {
+                    debug!("Failed to receive: {error}");
+                    break;
+                }
+            };
+            if self.peer.is_some_and(|peer| peer != sender) {
+                continue;
+            }
+            let message = std::str::from_utf8(&buffer[..length])
+                .map_err(|error| error.to_string())
+                .and_then(|data| ron::from_str(data).map_err(|error| error.to_string()));
+            match message {
+                Ok(message) => messages.push((sender, message)),
+                Err(error) => warn!("Ignoring malformed message from {sender}: {error}"),
+            }
+        }
+        messages
+    }
+}
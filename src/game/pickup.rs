@@ -0,0 +1,300 @@
+//! Pickups and thrown items.
+//! Players collect [`Pickup`]s by walking over them. Food, extra lives and score bonuses
+//! take effect right away, while crates and bottles are carried until attacking tosses them.
+//! Defeated grunts sometimes drop a pickup.
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    audio::sfx::Sfx,
+    combat::{AttackKind, Health, Hit, DEPTH_TOLERANCE},
+    depth::{Elevation, YSort},
+    lives::Lives,
+    rng::{GameRng, RngStream},
+    score::{AwardPoints, ScoreReason},
+    spawn::{
+        enemy::Enemy,
+        level::LevelBounds,
+        pickup::{PickupKind, SpawnPickup},
+        player::Player,
+    },
+    state::{CharacterState, StateEntered, StateMachine},
+    waves::GameMode,
+};
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Pickup, HeldItem, Projectile)>();
+    app.add_systems(
+        FixedUpdate,
+        (collect_pickups, move_projectiles)
+            .chain()
+            .in_set(AppSet::Update),
+    );
+    app.observe(show_held_item);
+    app.observe(hide_held_item);
+    app.observe(toss_item);
+    app.observe(drop_pickup);
+}
+
+/// An item lying on the ground, waiting to be collected.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Pickup(pub PickupKind);
+
+/// The item a character carries, tossed on their next attack.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct HeldItem {
+    pub kind: PickupKind,
+}
+
+/// Shows a [`HeldItem`] in the character's hands.
+#[derive(Component, Debug, Clone, Copy)]
+struct HeldItemVisual;
+
+/// A tossed item flying through the air.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct Projectile {
+    pub thrower: Entity,
+    /// Horizontal speed in world units per second.
+    pub velocity: f32,
+}
+
+impl MapEntities for Projectile {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.thrower = entity_mapper.map_entity(self.thrower);
+    }
+}
+
+/// How close a player has to get to collect a pickup, in world units.
+const PICKUP_RANGE: f32 = 80.0;
+/// How close a projectile has to get to hit a character, in world units.
+const PROJECTILE_RANGE: f32 = 60.0;
+/// How fast tossed items fly, in world units per second.
+const PROJECTILE_SPEED: f32 = 1400.0;
+/// How high above the ground tossed items fly, in world units.
+const PROJECTILE_ELEVATION: f32 = 120.0;
+/// How much health food restores.
+const FOOD_HEALTH: f32 = 30.0;
+/// How many points a score bonus is worth.
+const SCORE_BONUS: u32 = 500;
+/// How likely a defeated grunt is to drop a pickup.
+const DROP_CHANCE: f64 = 0.5;
+
+fn collect_pickups(
+    mut commands: Commands,
+    pickup_query: Query<(Entity, &Pickup, &Transform)>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &StateMachine,
+            &mut Health,
+            Option<&mut Lives>,
+            Has<HeldItem>,
+        ),
+        With<Player>,
+    >,
+) {
+    // Whoever picked up something to carry this tick, as the query doesn't see it yet.
+    let mut carrying = Vec::new();
+    for (pickup_entity, pickup, pickup_transform) in &pickup_query {
+        let position = pickup_transform.translation.xy();
+        for (entity, transform, machine, mut health, lives, holding) in &mut player_query {
+            let offset = transform.translation.xy() - position;
+            if offset.x.abs() > PICKUP_RANGE
+                || offset.y.abs() > DEPTH_TOLERANCE
+                || !machine.state().can_act()
+            {
+                continue;
+            }
+            match pickup.0 {
+                PickupKind::Food => {
+                    health.current = (health.current + FOOD_HEALTH).min(health.max);
+                }
+                PickupKind::ExtraLife => {
+                    if let Some(mut lives) = lives {
+                        lives.0 += 1;
+                    }
+                }
+                PickupKind::ScoreBonus => {
+                    commands.trigger_targets(
+                        AwardPoints {
+                            reason: ScoreReason::Bonus,
+                            points: SCORE_BONUS,
+                        },
+                        entity,
+                    );
+                }
+                PickupKind::Crate | PickupKind::Bottle => {
+                    // Hands are full.
+                    if holding || carrying.contains(&entity) {
+                        continue;
+                    }
+                    carrying.push(entity);
+                    commands.entity(entity).insert(HeldItem { kind: pickup.0 });
+                }
+            }
+            commands.entity(pickup_entity).despawn_recursive();
+            commands.trigger(Sfx::Pickup);
+            break;
+        }
+    }
+}
+
+fn show_held_item(
+    trigger: Trigger<OnAdd, HeldItem>,
+    mut commands: Commands,
+    held_query: Query<&HeldItem>,
+) {
+    let holder = trigger.entity();
+    let Ok(held) = held_query.get(holder) else {
+        return;
+    };
+    commands
+        .spawn((
+            Name::new("Held Item"),
+            SpriteBundle {
+                sprite: held.kind.sprite(),
+                // Held overhead. Characters are scaled up, so this is in texels.
+                transform: Transform::from_xyz(0.0, 34.0, 0.1).with_scale(Vec3::splat(0.125)),
+                ..default()
+            },
+            HeldItemVisual,
+        ))
+        .set_parent(holder);
+}
+
+fn hide_held_item(
+    trigger: Trigger<OnRemove, HeldItem>,
+    mut commands: Commands,
+    children_query: Query<&Children>,
+    visual_query: Query<(), With<HeldItemVisual>>,
+) {
+    // The holder may be despawning along with its children.
+    let Ok(children) = children_query.get(trigger.entity()) else {
+        return;
+    };
+    for &child in children
+        .iter()
+        .filter(|child| visual_query.contains(**child))
+    {
+        if let Some(visual) = commands.get_entity(child) {
+            visual.despawn_recursive();
+        }
+    }
+}
+
+/// Toss the held item when entering [`CharacterState::Attack`] with [`AttackKind::Toss`].
+fn toss_item(
+    trigger: Trigger<StateEntered>,
+    mut commands: Commands,
+    holder_query: Query<(&HeldItem, &Transform, &Sprite)>,
+) {
+    if trigger.event().0 != CharacterState::Attack(AttackKind::Toss) {
+        return;
+    }
+    let thrower = trigger.entity();
+    let Ok((held, transform, sprite)) = holder_query.get(thrower) else {
+        return;
+    };
+    let direction = if sprite.flip_x { -1.0 } else { 1.0 };
+
+    commands.entity(thrower).remove::<HeldItem>();
+    commands.spawn((
+        Name::new("Projectile"),
+        SpriteBundle {
+            sprite: held.kind.sprite(),
+            transform: Transform::from_translation(transform.translation),
+            ..default()
+        },
+        Projectile {
+            thrower,
+            velocity: direction * PROJECTILE_SPEED,
+        },
+        YSort,
+        Elevation(PROJECTILE_ELEVATION),
+        StateScoped(Screen::Playing),
+    ));
+    commands.trigger(Sfx::Throw);
+}
+
+fn move_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    bounds: Res<LevelBounds>,
+    mode: Res<GameMode>,
+    mut projectile_query: Query<(Entity, &Projectile, &mut Transform)>,
+    victim_query: Query<
+        (Entity, &StateMachine, &Transform, Has<Enemy>),
+        (With<Health>, Without<Projectile>),
+    >,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    for (entity, projectile, mut transform) in &mut projectile_query {
+        transform.translation.x += projectile.velocity * time.delta_seconds();
+        let position = transform.translation.xy();
+        if !bounds.contains(position) {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // Items thrown by one player fly past the other in co-op.
+        let friendly_fire = *mode == GameMode::Versus || enemy_query.contains(projectile.thrower);
+        let victim =
+            victim_query
+                .iter()
+                .find(|(victim, machine, victim_transform, victim_enemy)| {
+                    let offset = victim_transform.translation.xy() - position;
+                    *victim != projectile.thrower
+                        && (*victim_enemy || friendly_fire)
+                        && machine.state().is_vulnerable()
+                        && offset.x.abs() <= PROJECTILE_RANGE
+                        && offset.y.abs() <= DEPTH_TOLERANCE
+                });
+        if let Some((victim, ..)) = victim {
+            commands.entity(entity).despawn_recursive();
+            commands.trigger(Hit {
+                attacker: projectile.thrower,
+                victim,
+                kind: AttackKind::Toss,
+                direction: projectile.velocity.signum(),
+            });
+        }
+    }
+}
+
+/// Defeated grunts sometimes drop a pickup where they fell.
+fn drop_pickup(
+    trigger: Trigger<StateEntered>,
+    mut commands: Commands,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    mut rng: ResMut<GameRng>,
+) {
+    if trigger.event().0 != CharacterState::Dead {
+        return;
+    }
+    let Ok(transform) = enemy_query.get(trigger.entity()) else {
+        return;
+    };
+    let rng = rng.stream(RngStream::Gameplay);
+    if !rng.gen_bool(DROP_CHANCE) {
+        return;
+    }
+    let kind = *[PickupKind::Food, PickupKind::ScoreBonus, PickupKind::Bottle]
+        .choose(rng)
+        .unwrap();
+    commands.trigger(SpawnPickup {
+        kind,
+        translation: transform.translation.xy(),
+    });
+}
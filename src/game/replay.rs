@@ -0,0 +1,466 @@
+//! Recording and replaying matches.
+//! Every tick of a match, what the players' controllers ask for is recorded, along with how the
+//! match was set up and the seed its randomness starts from. Since the simulation runs in fixed
+//! ticks, feeding the same inputs back in place of the keyboard plays out the same match.
+//! Replays can be paused, stepped through a tick at a time, sped up and scrubbed.
+
+use std::time::Duration;
+
+use bevy::{app::FixedMain, ecs::world::Command, prelude::*, ui::Val::*};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    action::ActionController,
+    ai::{drive_ai, Difficulty, Opponent},
+    assets::{FontAsset, FontAssets},
+    movement::MovementController,
+    net::NetSession,
+    rng::{seed_match, MatchSeed},
+    save::RestoreMatch,
+    spawn::player::Player,
+    storage::Storage,
+    waves::GameMode,
+};
+use crate::{
+    screen::Screen,
+    ui::localization::{Localized, LocalizedText, UiText},
+    AppSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        (
+            // A resumed match can't be replayed from its start,
+            // and rolling back networked matches would record ticks twice.
+            start_recording.after(seed_match).run_if(
+                not(resource_exists::<ReplayPlayback>)
+                    .and_then(not(resource_exists::<RestoreMatch>))
+                    .and_then(not(resource_exists::<NetSession>)),
+            ),
+            spawn_replay_ui.run_if(resource_exists::<ReplayPlayback>),
+        ),
+    );
+    app.add_systems(
+        OnExit(Screen::Playing),
+        (save_recording, reset_playback_speed),
+    );
+    app.add_systems(OnEnter(Screen::Title), stop_replay);
+
+    app.add_systems(
+        FixedUpdate,
+        (
+            record_tick
+                .in_set(AppSet::BufferInput)
+                .run_if(resource_exists::<ReplayRecording>),
+            // Take over from whatever else controls the players.
+            play_back_tick
+                .in_set(AppSet::RecordInput)
+                .after(drive_ai)
+                .run_if(resource_exists::<ReplayPlayback>),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+    app.add_systems(
+        Update,
+        (control_playback, apply_playback_speed, update_replay_ui)
+            .chain()
+            .run_if(in_state(Screen::Playing).and_then(resource_exists::<ReplayPlayback>)),
+    );
+}
+
+/// A recorded match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub mode: GameMode,
+    pub opponent: Opponent,
+    pub difficulty: Difficulty,
+    /// The inputs of every tick, as runs of identical ticks to keep replays small.
+    runs: Vec<(u32, [TickInput; 2])>,
+}
+
+impl Replay {
+    const STORAGE_KEY: &'static str = "replay";
+
+    /// How many ticks were recorded.
+    fn len(&self) -> u32 {
+        self.runs.iter().map(|(count, _)| count).sum()
+    }
+
+    fn push(&mut self, inputs: [TickInput; 2]) {
+        match self.runs.last_mut() {
+            Some((count, last)) if *last == inputs => *count += 1,
+            _ => self.runs.push((1, inputs)),
+        }
+    }
+}
+
+/// What a player's controllers asked for during a tick:
+/// the movement intent, and the actions packed into bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub(super) struct TickInput(f32, f32, u8);
+
+impl TickInput {
+    const RUN: u8 = 1 << 0;
+    const JUMP: u8 = 1 << 1;
+    const ATTACK: u8 = 1 << 2;
+    const BLOCK: u8 = 1 << 3;
+    const GRAB: u8 = 1 << 4;
+
+    pub(super) fn new(movement: &MovementController, action: &ActionController) -> Self {
+        let actions = [
+            (action.run, Self::RUN),
+            (action.jump, Self::JUMP),
+            (action.attack, Self::ATTACK),
+            (action.block, Self::BLOCK),
+            (action.grab, Self::GRAB),
+        ]
+        .into_iter()
+        .filter(|(active, _)| *active)
+        .fold(0, |actions, (_, bit)| actions | bit);
+        Self(movement.0.x, movement.0.y, actions)
+    }
+
+    /// Only what is held down, without the presses that fire once.
+    pub(super) fn held(self) -> Self {
+        let Self(x, y, actions) = self;
+        Self(x, y, actions & (Self::RUN | Self::BLOCK))
+    }
+
+    pub(super) fn apply(self, movement: &mut MovementController, action: &mut ActionController) {
+        let Self(x, y, actions) = self;
+        movement.0 = Vec2::new(x, y);
+        *action = ActionController {
+            run: actions & Self::RUN != 0,
+            jump: actions & Self::JUMP != 0,
+            attack: actions & Self::ATTACK != 0,
+            block: actions & Self::BLOCK != 0,
+            grab: actions & Self::GRAB != 0,
+        };
+    }
+}
+
+/// Whether there is a recorded match to watch.
+pub fn has_replay(storage: &Storage) -> bool {
+    storage.read(Replay::STORAGE_KEY).is_some()
+}
+
+/// The match being recorded.
+#[derive(Resource, Debug)]
+pub struct ReplayRecording(Replay);
+
+/// Present while watching a replay.
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    /// How many ticks the replay has.
+    length: u32,
+    /// How many ticks were played back so far.
+    tick: u32,
+    /// Where the next tick is: the index into [`Replay::runs`],
+    /// and how many ticks of that run were already played back.
+    run: usize,
+    run_progress: u32,
+    paused: bool,
+    speed: f64,
+    /// The tick to fast-forward to.
+    seek: Option<u32>,
+}
+
+impl ReplayPlayback {
+    const MIN_SPEED: f64 = 0.25;
+    const MAX_SPEED: f64 = 8.0;
+    /// How fast to fast-forward while seeking.
+    const SEEK_SPEED: f64 = 16.0;
+    /// How far scrubbing jumps.
+    const SCRUB_SECONDS: f64 = 5.0;
+
+    fn new(replay: Replay) -> Self {
+        Self {
+            length: replay.len(),
+            replay,
+            tick: 0,
+            run: 0,
+            run_progress: 0,
+            paused: false,
+            speed: 1.0,
+            seek: None,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    fn finished(&self) -> bool {
+        self.run >= self.replay.runs.len()
+    }
+
+    fn next_inputs(&mut self) -> Option<[TickInput; 2]> {
+        let (count, inputs) = *self.replay.runs.get(self.run)?;
+        self.tick += 1;
+        self.run_progress += 1;
+        if self.run_progress >= count {
+            self.run += 1;
+            self.run_progress = 0;
+        }
+        Some(inputs)
+    }
+
+    fn rewind(&mut self) {
+        self.tick = 0;
+        self.run = 0;
+        self.run_progress = 0;
+    }
+}
+
+/// Load the last recorded match and start watching it.
+pub struct WatchReplay;
+
+impl Command for WatchReplay {
+    fn apply(self, world: &mut World) {
+        let Some(data) = world.resource::<Storage>().read(Replay::STORAGE_KEY) else {
+            return;
+        };
+        let replay: Replay = match ron::from_str(&data) {
+            Ok(replay) => replay,
+            Err(error) => {
+                warn!("Discarding unreadable replay: {error}");
+                world.resource_mut::<Storage>().remove(Replay::STORAGE_KEY);
+                return;
+            }
+        };
+        world.insert_resource(replay.mode);
+        world.insert_resource(replay.opponent);
+        world.insert_resource(replay.difficulty);
+        world.insert_resource(ReplayPlayback::new(replay));
+        world
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Playing);
+    }
+}
+
+fn start_recording(
+    mut commands: Commands,
+    seed: Res<MatchSeed>,
+    mode: Res<GameMode>,
+    opponent: Res<Opponent>,
+    difficulty: Res<Difficulty>,
+) {
+    commands.insert_resource(ReplayRecording(Replay {
+        seed: seed.0,
+        mode: *mode,
+        opponent: *opponent,
+        difficulty: *difficulty,
+        runs: Vec::new(),
+    }));
+}
+
+fn record_tick(
+    mut recording: ResMut<ReplayRecording>,
+    controller_query: Query<(&Player, &MovementController, &ActionController)>,
+) {
+    let mut inputs = [TickInput::default(); 2];
+    for (player, movement, action) in &controller_query {
+        inputs[player.index()] = TickInput::new(movement, action);
+    }
+    recording.0.push(inputs);
+}
+
+fn save_recording(
+    mut commands: Commands,
+    mut storage: ResMut<Storage>,
+    recording: Option<Res<ReplayRecording>>,
+) {
+    let Some(recording) = recording else {
+        return;
+    };
+    commands.remove_resource::<ReplayRecording>();
+    let data = match ron::to_string(&recording.0) {
+        Ok(data) => data,
+        Err(error) => {
+            error!("Failed to serialize the replay: {error}");
+            return;
+        }
+    };
+    if let Err(error) = storage.write(Replay::STORAGE_KEY, &data) {
+        error!("Failed to save the replay: {error}");
+    }
+}
+
+fn play_back_tick(
+    mut playback: ResMut<ReplayPlayback>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut controller_query: Query<(&Player, &mut MovementController, &mut ActionController)>,
+) {
+    let inputs = playback.next_inputs().unwrap_or_default();
+    for (player, mut movement, mut action) in &mut controller_query {
+        inputs[player.index()].apply(&mut movement, &mut action);
+    }
+
+    // Stop right at the tick being sought, or at the end, instead of finishing the frame's ticks.
+    let reached = playback.seek.is_some_and(|target| playback.tick >= target);
+    if reached {
+        playback.seek = None;
+    }
+    if reached || playback.finished() {
+        fixed_time.discard_overstep(Duration::MAX);
+    }
+}
+
+fn control_playback(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    fixed_time: Res<Time<Fixed>>,
+    mut playback: ResMut<ReplayPlayback>,
+) {
+    if input.just_pressed(KeyCode::Space) {
+        playback.paused = !playback.paused;
+    }
+    if input.just_pressed(KeyCode::Period) && playback.paused && !playback.finished() {
+        commands.add(StepTick);
+    }
+    if input.just_pressed(KeyCode::ArrowUp) {
+        playback.speed = (playback.speed * 2.0).min(ReplayPlayback::MAX_SPEED);
+    }
+    if input.just_pressed(KeyCode::ArrowDown) {
+        playback.speed = (playback.speed / 2.0).max(ReplayPlayback::MIN_SPEED);
+    }
+
+    let scrub = (ReplayPlayback::SCRUB_SECONDS / fixed_time.timestep().as_secs_f64()) as u32;
+    let position = playback.seek.unwrap_or(playback.tick);
+    if input.just_pressed(KeyCode::ArrowRight) {
+        playback.seek = Some((position + scrub).min(playback.length));
+    }
+    if input.just_pressed(KeyCode::ArrowLeft) {
+        // The simulation can't run backwards, so start over and fast-forward instead.
+        playback.seek = Some(position.saturating_sub(scrub));
+        commands.add(RestartReplay);
+    }
+}
+
+fn apply_playback_speed(playback: Res<ReplayPlayback>, mut time: ResMut<Time<Virtual>>) {
+    if playback.seek.is_some() {
+        time.unpause();
+        time.set_relative_speed_f64(ReplayPlayback::SEEK_SPEED);
+    } else if playback.paused || playback.finished() {
+        time.pause();
+    } else {
+        time.unpause();
+        time.set_relative_speed_f64(playback.speed);
+    }
+}
+
+fn reset_playback_speed(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+    time.set_relative_speed(1.0);
+}
+
+fn stop_replay(mut commands: Commands) {
+    commands.remove_resource::<ReplayPlayback>();
+}
+
+/// Run a single tick while paused.
+struct StepTick;
+
+impl Command for StepTick {
+    fn apply(self, world: &mut World) {
+        let mut fixed_time = world.resource_mut::<Time<Fixed>>();
+        let timestep = fixed_time.timestep();
+        fixed_time.advance_by(timestep);
+        *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+        world.run_schedule(FixedMain);
+        *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+    }
+}
+
+/// Start the replayed match over without leaving the screen.
+struct RestartReplay;
+
+impl Command for RestartReplay {
+    fn apply(self, world: &mut World) {
+        world.run_schedule(OnExit(Screen::Playing));
+        let scoped: Vec<Entity> = world
+            .query::<(Entity, &StateScoped<Screen>)>()
+            .iter(world)
+            .filter(|(_, scope)| scope.0 == Screen::Playing)
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in scoped {
+            // Some may have gone with their parents already.
+            if let Some(entity) = world.get_entity_mut(entity) {
+                entity.despawn_recursive();
+            }
+        }
+        world.resource_mut::<ReplayPlayback>().rewind();
+        world.run_schedule(OnEnter(Screen::Playing));
+    }
+}
+
+#[derive(Component)]
+struct ReplayUi;
+
+fn spawn_replay_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    let style = TextStyle {
+        font: fonts[&FontAsset::Pixel].clone_weak(),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+    commands
+        .spawn((
+            Name::new("Replay UI"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Px(10.0),
+                    left: Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|children| {
+            children.spawn((
+                Name::new("Replay Status"),
+                TextBundle::from_section("", style.clone()),
+                LocalizedText::new(Localized::new("replay-status")),
+                ReplayUi,
+            ));
+            children.spawn((
+                Name::new("Replay Controls"),
+                TextBundle::from_section("", style),
+                LocalizedText::new(Localized::new("replay-controls")),
+            ));
+        });
+}
+
+fn update_replay_ui(
+    playback: Res<ReplayPlayback>,
+    fixed_time: Res<Time<Fixed>>,
+    mut ui_query: Query<&mut LocalizedText, With<ReplayUi>>,
+) {
+    let seconds = |ticks: u32| {
+        let seconds = ticks as f64 * fixed_time.timestep().as_secs_f64();
+        format!("{seconds:.1}")
+    };
+    let state = if playback.finished() {
+        Localized::new("replay-end").into()
+    } else if playback.paused {
+        Localized::new("replay-paused").into()
+    } else {
+        UiText::from("")
+    };
+    let status = Localized::new("replay-status")
+        .with_arg("time", seconds(playback.tick))
+        .with_arg("length", seconds(playback.length))
+        .with_arg("speed", playback.speed.to_string())
+        .with_arg("state", state);
+    for mut text in &mut ui_query {
+        if text.text != status {
+            text.text = status.clone();
+        }
+    }
+}
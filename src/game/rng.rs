@@ -0,0 +1,100 @@
+//! Seeded randomness.
+//! Everything random draws from [`GameRng`], which is reseeded from the [`MatchSeed`] whenever a
+//! match starts. Given the same seed and inputs, a match plays out the same way.
+//! Each subsystem has its own [`RngStream`], so that e.g. how many sounds play
+//! doesn't change what the AI decides.
+//!
+//! Pass `--seed <seed>` on the command line to play every match with the same seed.
+//! Replays bring their own seed, and networked matches use the host's.
+
+use bevy::prelude::*;
+use rand_chacha::{rand_core::SeedableRng, ChaCha8Rng};
+
+use super::{net::NetSession, replay::ReplayPlayback};
+use crate::screen::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    let argument = SeedArgument(seed_argument());
+    app.insert_resource(GameRng::new(argument.0.unwrap_or_else(rand::random)));
+    app.insert_resource(argument);
+    app.register_type::<MatchSeed>();
+    app.init_resource::<MatchSeed>();
+    app.add_systems(OnEnter(Screen::Playing), seed_match);
+}
+
+/// The seed the current match's randomness starts from.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MatchSeed(pub u64);
+
+/// The independent streams of random numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    /// Anything that affects how a match plays out.
+    Gameplay,
+    /// Decisions of computer-controlled characters.
+    Ai,
+    Audio,
+    Camera,
+}
+
+/// Random number generators for every [`RngStream`], all forked from one seed.
+#[derive(Resource, Debug, Clone)]
+pub struct GameRng {
+    streams: [ChaCha8Rng; 4],
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        let fork = |stream: RngStream| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            rng.set_stream(stream as u64);
+            rng
+        };
+        Self {
+            streams: [
+                fork(RngStream::Gameplay),
+                fork(RngStream::Ai),
+                fork(RngStream::Audio),
+                fork(RngStream::Camera),
+            ],
+        }
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut ChaCha8Rng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+/// The seed passed on the command line, if any.
+#[derive(Resource, Debug, Clone, Copy)]
+pub(super) struct SeedArgument(Option<u64>);
+
+fn seed_argument() -> Option<u64> {
+    let mut args = std::env::args().skip_while(|arg| arg != "--seed").skip(1);
+    let seed = args.next()?;
+    match seed.parse() {
+        Ok(seed) => Some(seed),
+        Err(error) => {
+            warn!("Ignoring invalid seed {seed}: {error}");
+            None
+        }
+    }
+}
+
+/// Pick the seed for a new match: the replay's, the one agreed on with the other player
+/// of a networked match, the command line's, or a fresh one.
+pub(super) fn seed_match(
+    mut seed: ResMut<MatchSeed>,
+    mut rng: ResMut<GameRng>,
+    argument: Res<SeedArgument>,
+    playback: Option<Res<ReplayPlayback>>,
+    session: Option<Res<NetSession>>,
+) {
+    seed.0 = playback
+        .map(|playback| playback.seed())
+        .or(session.map(|session| session.seed()))
+        .or(argument.0)
+        .unwrap_or_else(rand::random);
+    *rng = GameRng::new(seed.0);
+}
@@ -0,0 +1,147 @@
+//! Ending rounds.
+//! Whatever decides a round triggers [`RoundOver`] once. The outcome is announced on screen
+//! for a moment before moving on to the results screen.
+
+use std::time::Duration;
+
+use bevy::{ecs::world::Command, prelude::*};
+
+use super::{
+    lives::Lives,
+    spawn::player::Player,
+    state::{CharacterState, StateMachine},
+    waves::GameMode,
+};
+use crate::{screen::Screen, ui::prelude::*, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Playing), reset_round_end);
+    app.add_systems(
+        Update,
+        tick_round_end
+            .in_set(AppSet::TickTimers)
+            .run_if(in_state(Screen::Playing)),
+    );
+    app.add_systems(
+        FixedUpdate,
+        check_knockout
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing).and_then(resource_equals(GameMode::Versus))),
+    );
+    app.observe(end_round);
+}
+
+/// How a round ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum RoundOutcome {
+    /// Every wave was cleared.
+    Cleared,
+    /// Every player ran out of lives.
+    Defeated,
+    /// The last player standing won a versus match.
+    Won(Player),
+    /// Everybody went down at once.
+    Draw,
+}
+
+impl RoundOutcome {
+    pub fn text(self) -> Localized {
+        match self {
+            RoundOutcome::Cleared => Localized::new("stage-clear"),
+            RoundOutcome::Defeated => Localized::new("game-over"),
+            RoundOutcome::Won(player) => {
+                Localized::new("player-wins").with_arg("player", player.name())
+            }
+            RoundOutcome::Draw => Localized::new("draw"),
+        }
+    }
+}
+
+/// Triggered once when the round ends.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RoundOver(pub RoundOutcome);
+
+/// Present once the round is over,
+/// counting down to moving on to the results screen.
+#[derive(Resource, Debug, Clone)]
+pub struct RoundEnd(Timer);
+
+/// Announces the outcome of the round.
+#[derive(Component, Debug)]
+struct RoundOverUi;
+
+/// Take back the end of the round, for rolling back to before it ended.
+pub struct UndoRoundOver;
+
+impl Command for UndoRoundOver {
+    fn apply(self, world: &mut World) {
+        world.remove_resource::<RoundEnd>();
+        let announcements: Vec<Entity> = world
+            .query_filtered::<Entity, With<RoundOverUi>>()
+            .iter(world)
+            .collect();
+        for entity in announcements {
+            world.entity_mut(entity).despawn_recursive();
+        }
+    }
+}
+
+/// How long the outcome is shown before moving on to the results screen.
+const ROUND_END_DELAY: Duration = Duration::from_secs(4);
+
+/// Whether a character is down for good.
+pub fn is_out(machine: &StateMachine, lives: Option<&Lives>) -> bool {
+    machine.state() == CharacterState::Dead && lives.is_none_or(|lives| lives.0 == 0)
+}
+
+fn reset_round_end(mut commands: Commands) {
+    commands.remove_resource::<RoundEnd>();
+}
+
+/// End a versus match once at most one player is left standing.
+fn check_knockout(
+    mut commands: Commands,
+    round_end: Option<Res<RoundEnd>>,
+    player_query: Query<(&Player, &StateMachine, Option<&Lives>)>,
+) {
+    if round_end.is_some() || player_query.iter().count() < 2 {
+        return;
+    }
+    let mut standing = player_query
+        .iter()
+        .filter(|(_, machine, lives)| !is_out(machine, *lives))
+        .map(|(player, ..)| *player);
+    let outcome = match (standing.next(), standing.next()) {
+        (None, _) => RoundOutcome::Draw,
+        (Some(player), None) => RoundOutcome::Won(player),
+        _ => return,
+    };
+    commands.trigger(RoundOver(outcome));
+}
+
+fn end_round(trigger: Trigger<RoundOver>, mut commands: Commands) {
+    commands
+        .ui_root()
+        .insert((
+            Name::new("Round Over"),
+            RoundOverUi,
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|children| {
+            children.header(trigger.event().0.text());
+        });
+    commands.insert_resource(RoundEnd(Timer::new(ROUND_END_DELAY, TimerMode::Once)));
+}
+
+fn tick_round_end(
+    time: Res<Time>,
+    round_end: Option<ResMut<RoundEnd>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let Some(mut round_end) = round_end else {
+        return;
+    };
+    if round_end.0.tick(time.delta()).just_finished() {
+        next_screen.set(Screen::Results);
+    }
+}
@@ -0,0 +1,403 @@
+//! Saving and resuming matches.
+//! Leaving a match early saves the characters, items and match progress as a [`DynamicScene`],
+//! using the types registered for reflection. Resuming spawns the saved [`Level`]
+//! and then restores everything from the save on top of it.
+//! The save is discarded once the match ends.
+//!
+//! Networked matches use the same [`capture`] and [`restore`] to roll back.
+
+use bevy::{
+    ecs::{
+        entity::EntityHashMap,
+        reflect::{ReflectMapEntities, ReflectResource},
+        world::Command,
+    },
+    prelude::*,
+    scene::{serde::SceneDeserializer, DynamicEntity, SceneFilter},
+};
+use serde::de::DeserializeSeed;
+
+use super::{
+    action::ActionController,
+    ai::{AiController, Difficulty, Opponent},
+    animation::PlayerAnimation,
+    combat::{Health, HitVictims, Knockback},
+    combo::InputBuffer,
+    depth::{Elevation, YSort},
+    grab::{GrabbedBy, Grabbing},
+    jump::Airborne,
+    lives::Lives,
+    movement::{Movement, MovementController},
+    pickup::{HeldItem, Pickup, Projectile},
+    replay::ReplayPlayback,
+    round::{RoundEnd, RoundOver},
+    score::{MatchResults, Score, ScoreChanged, ScoreMultiplier},
+    spawn::{
+        enemy::{Enemy, SpawnEnemy},
+        level::Level,
+        pickup::SpawnPickup,
+        player::{Player, SpawnPlayer},
+    },
+    state::StateMachine,
+    storage::Storage,
+    waves::{GameMode, SpawnTrigger, WaveProgress},
+};
+use crate::screen::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    // Restore before the first tick of the match.
+    app.add_systems(
+        FixedPreUpdate,
+        restore_match.run_if(in_state(Screen::Playing).and_then(resource_exists::<RestoreMatch>)),
+    );
+    app.observe(discard_save);
+}
+
+const STORAGE_KEY: &str = "match";
+
+/// Everything that takes part in a match.
+type MatchEntity = Or<(
+    With<Player>,
+    With<Enemy>,
+    With<Pickup>,
+    With<SpawnTrigger>,
+    With<Projectile>,
+)>;
+
+/// The components that make up the state of a match.
+fn component_filter() -> SceneFilter {
+    SceneFilter::deny_all()
+        .allow::<Player>()
+        .allow::<Enemy>()
+        .allow::<Pickup>()
+        .allow::<SpawnTrigger>()
+        .allow::<Projectile>()
+        .allow::<Transform>()
+        .allow::<Sprite>()
+        .allow::<Movement>()
+        .allow::<MovementController>()
+        .allow::<ActionController>()
+        .allow::<AiController>()
+        .allow::<PlayerAnimation>()
+        .allow::<StateMachine>()
+        .allow::<InputBuffer>()
+        .allow::<Health>()
+        .allow::<HitVictims>()
+        .allow::<Lives>()
+        .allow::<Score>()
+        .allow::<ScoreMultiplier>()
+        .allow::<Elevation>()
+        .allow::<Airborne>()
+        .allow::<Knockback>()
+        .allow::<Grabbing>()
+        .allow::<GrabbedBy>()
+        .allow::<HeldItem>()
+}
+
+/// The resources that make up the state of a match.
+fn resource_filter() -> SceneFilter {
+    SceneFilter::deny_all()
+        .allow::<GameMode>()
+        .allow::<Level>()
+        .allow::<Opponent>()
+        .allow::<Difficulty>()
+        .allow::<WaveProgress>()
+        .allow::<MatchResults>()
+}
+
+/// Capture the state of the current match.
+pub(super) fn capture(world: &mut World) -> DynamicScene {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, MatchEntity>()
+        .iter(world)
+        .collect();
+    DynamicSceneBuilder::from_world(world)
+        .with_filter(component_filter())
+        .with_resource_filter(resource_filter())
+        .extract_entities(entities.into_iter())
+        .extract_resources()
+        .build()
+}
+
+/// Whether there is a match to resume.
+pub fn has_saved_match(storage: &Storage) -> bool {
+    storage.read(STORAGE_KEY).is_some()
+}
+
+/// Save the current match, unless it is already over.
+pub struct SaveMatch;
+
+impl Command for SaveMatch {
+    fn apply(self, world: &mut World) {
+        if world.contains_resource::<RoundEnd>() {
+            world.resource_mut::<Storage>().remove(STORAGE_KEY);
+            return;
+        }
+        let scene = capture(world);
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let data = match scene.serialize(&registry.read()) {
+            Ok(data) => data,
+            Err(error) => {
+                error!("Failed to serialize the match: {error}");
+                return;
+            }
+        };
+        if let Err(error) = world.resource_mut::<Storage>().write(STORAGE_KEY, &data) {
+            error!("Failed to save the match: {error}");
+        }
+    }
+}
+
+/// Load the saved match and start playing it.
+pub struct ResumeMatch;
+
+impl Command for ResumeMatch {
+    fn apply(self, world: &mut World) {
+        let Some(data) = world.resource::<Storage>().read(STORAGE_KEY) else {
+            return;
+        };
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let scene = {
+            let registry = registry.read();
+            let deserializer = SceneDeserializer {
+                type_registry: &registry,
+            };
+            let result = ron::Deserializer::from_str(&data)
+                .map_err(ron::Error::from)
+                .and_then(|mut ron| deserializer.deserialize(&mut ron));
+            match result {
+                Ok(scene) => scene,
+                Err(error) => {
+                    warn!("Discarding unreadable saved match: {error}");
+                    world.resource_mut::<Storage>().remove(STORAGE_KEY);
+                    return;
+                }
+            }
+        };
+
+        // Which level gets spawned depends on these.
+        apply_resources(world, &scene);
+        world.insert_resource(RestoreMatch(scene));
+        world
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Playing);
+    }
+}
+
+/// A saved match waiting for its level to be spawned.
+#[derive(Resource)]
+pub struct RestoreMatch(DynamicScene);
+
+fn apply_resources(world: &mut World, scene: &DynamicScene) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for resource in &scene.resources {
+        let reflect_resource = resource
+            .get_represented_type_info()
+            .and_then(|info| registry.get_type_data::<ReflectResource>(info.type_id()));
+        match reflect_resource {
+            Some(reflect_resource) => {
+                reflect_resource.apply_or_insert(world, &**resource, &registry);
+            }
+            None => warn!(
+                "Skipping unknown saved resource {}",
+                resource.reflect_type_path()
+            ),
+        }
+    }
+}
+
+/// Put the saved match on top of the freshly spawned level.
+fn restore_match(world: &mut World) {
+    let Some(RestoreMatch(scene)) = world.remove_resource::<RestoreMatch>() else {
+        return;
+    };
+    // Entering the screen reset the match progress.
+    restore(world, &scene);
+}
+
+/// Bring the match back to the state [`capture`]d in `scene`.
+/// Entities that are still around are reused, everything else is spawned or despawned.
+pub(super) fn restore(world: &mut World, scene: &DynamicScene) {
+    apply_resources(world, scene);
+
+    let live: Vec<Entity> = world
+        .query_filtered::<Entity, MatchEntity>()
+        .iter(world)
+        .collect();
+    let mut entity_map = EntityHashMap::default();
+    for saved in &scene.entities {
+        if let Some(entity) = find_target(world, &live, saved) {
+            entity_map.insert(saved.entity, entity);
+        }
+    }
+    for entity in live {
+        let stale = !world.entity(entity).contains::<Player>()
+            && !entity_map.values().any(|target| *target == entity);
+        if stale {
+            world.entity_mut(entity).despawn_recursive();
+        }
+    }
+    for saved in &scene.entities {
+        if entity_map.contains_key(&saved.entity) {
+            continue;
+        }
+        if let Some(entity) = spawn_target(world, saved) {
+            entity_map.insert(saved.entity, entity);
+        }
+    }
+
+    let filter = component_filter();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for saved in &scene.entities {
+        let Some(&entity) = entity_map.get(&saved.entity) else {
+            continue;
+        };
+        let saved_types: Vec<_> = saved
+            .components
+            .iter()
+            .filter_map(|component| component.get_represented_type_info())
+            .map(|info| info.type_id())
+            .collect();
+        // Drop what was added since.
+        let added: Vec<_> = world
+            .entity(entity)
+            .archetype()
+            .components()
+            .filter_map(|id| world.components().get_info(id)?.type_id())
+            .filter(|type_id| filter.is_allowed_by_id(*type_id) && !saved_types.contains(type_id))
+            .collect();
+        for type_id in added {
+            if let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_id) {
+                reflect_component.remove(&mut world.entity_mut(entity));
+            }
+        }
+
+        let mut entity = world.entity_mut(entity);
+        for component in &saved.components {
+            let reflect_component = component
+                .get_represented_type_info()
+                .and_then(|info| registry.get_type_data::<ReflectComponent>(info.type_id()));
+            if let Some(reflect_component) = reflect_component {
+                reflect_component.apply_or_insert(&mut entity, &**component, &registry);
+            }
+        }
+    }
+
+    // Point references between entities at their restored counterparts.
+    let targets: Vec<Entity> = entity_map.values().copied().collect();
+    for registration in registry.iter() {
+        if !filter.is_allowed_by_id(registration.type_id()) {
+            continue;
+        }
+        if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+            map_entities.map_entities(world, &mut entity_map, &targets);
+        }
+    }
+
+    // Let the UI catch up.
+    let scores: Vec<(Player, Score)> = world
+        .query::<(&Player, &Score)>()
+        .iter(world)
+        .map(|(player, score)| (*player, *score))
+        .collect();
+    for (player, score) in scores {
+        world.trigger(ScoreChanged {
+            player,
+            score: score.0,
+        });
+    }
+    world.flush();
+}
+
+/// Find the live entity to restore a saved one into.
+fn find_target(world: &mut World, live: &[Entity], saved: &DynamicEntity) -> Option<Entity> {
+    if let Some(player) = saved_component::<Player>(saved) {
+        return world
+            .query::<(Entity, &Player)>()
+            .iter(world)
+            .find(|(_, other)| **other == player)
+            .map(|(entity, _)| entity);
+    }
+    // Saves from earlier sessions may reuse the ids of unrelated entities.
+    let entity = world.get_entity(saved.entity)?;
+    let same_kind = live.contains(&saved.entity)
+        && entity.contains::<Enemy>() == is_saved::<Enemy>(saved)
+        && entity.contains::<Pickup>() == is_saved::<Pickup>(saved)
+        && entity.contains::<SpawnTrigger>() == is_saved::<SpawnTrigger>(saved)
+        && entity.contains::<Projectile>() == is_saved::<Projectile>(saved);
+    same_kind.then_some(saved.entity)
+}
+
+/// Spawn an entity to restore a saved one into.
+fn spawn_target(world: &mut World, saved: &DynamicEntity) -> Option<Entity> {
+    let translation = saved_component::<Transform>(saved)
+        .map_or(Vec2::ZERO, |transform| transform.translation.xy());
+
+    if let Some(player) = saved_component::<Player>(saved) {
+        return spawn_with::<Player>(
+            world,
+            SpawnPlayer {
+                player,
+                translation,
+            },
+        );
+    }
+    if is_saved::<Enemy>(saved) {
+        return spawn_with::<Enemy>(world, SpawnEnemy { translation });
+    }
+    if let Some(Pickup(kind)) = saved_component::<Pickup>(saved) {
+        return spawn_with::<Pickup>(world, SpawnPickup { kind, translation });
+    }
+    if is_saved::<SpawnTrigger>(saved) {
+        let entity = world.spawn((Name::new("Spawn Trigger"), StateScoped(Screen::Playing)));
+        return Some(entity.id());
+    }
+    if is_saved::<Projectile>(saved) {
+        // The saved components bring the sprite and movement.
+        let entity = world.spawn((
+            Name::new("Projectile"),
+            SpriteBundle::default(),
+            YSort,
+            StateScoped(Screen::Playing),
+        ));
+        return Some(entity.id());
+    }
+    None
+}
+
+/// Trigger a spawn event and return the new entity with a `T`.
+fn spawn_with<T: Component>(world: &mut World, event: impl Event) -> Option<Entity> {
+    let mut query = world.query_filtered::<Entity, With<T>>();
+    let existing: Vec<Entity> = query.iter(world).collect();
+    world.trigger(event);
+    world.flush();
+    query.iter(world).find(|entity| !existing.contains(entity))
+}
+
+fn is_saved<T: Reflect + TypePath>(saved: &DynamicEntity) -> bool {
+    saved
+        .components
+        .iter()
+        .any(|component| component.represents::<T>())
+}
+
+fn saved_component<T: FromReflect + TypePath>(saved: &DynamicEntity) -> Option<T> {
+    saved
+        .components
+        .iter()
+        .find(|component| component.represents::<T>())
+        .and_then(|component| T::from_reflect(&**component))
+}
+
+/// A finished match can't be resumed.
+fn discard_save(
+    _trigger: Trigger<RoundOver>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut storage: ResMut<Storage>,
+) {
+    if playback.is_none() {
+        storage.remove(STORAGE_KEY);
+    }
+}
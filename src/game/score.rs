@@ -0,0 +1,276 @@
+//! Player scores.
+//! Players earn points for landing hits, keeping combos going, knocking characters out
+//! and winning rounds. Quick successive hits raise a score multiplier,
+//! which decays back down once the player stops hitting.
+//! Everything is tallied in [`MatchResults`] for the results screen.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    combat::HitLanded,
+    round::{RoundOutcome, RoundOver},
+    spawn::player::Player,
+    state::{CharacterState, StateMachine},
+};
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Score, ScoreMultiplier, MatchResults)>();
+    app.init_resource::<MatchResults>();
+    app.add_systems(OnEnter(Screen::Playing), reset_match_results);
+    app.add_systems(FixedUpdate, decay_multipliers.in_set(AppSet::TickTimers));
+    app.observe(add_player_results);
+    app.observe(score_hit);
+    app.observe(score_round);
+    app.observe(award_points);
+}
+
+/// The points a player has earned this match.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Deref, DerefMut)]
+#[reflect(Component)]
+pub struct Score(pub u32);
+
+/// Scales the points a player earns from fighting while they keep up a combo.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ScoreMultiplier {
+    pub value: f32,
+    /// How many hits in a row the player landed, each shortly after the last.
+    pub combo: u32,
+    since_hit: Duration,
+}
+
+impl Default for ScoreMultiplier {
+    fn default() -> Self {
+        Self {
+            value: 1.0,
+            combo: 0,
+            since_hit: Duration::ZERO,
+        }
+    }
+}
+
+impl ScoreMultiplier {
+    /// How long after a hit the next one still continues the combo.
+    const COMBO_WINDOW: Duration = Duration::from_secs(1);
+    /// How much each hit of a combo raises the multiplier.
+    const STEP: f32 = 0.25;
+    const MAX: f32 = 4.0;
+    /// How quickly the multiplier falls back to `1.0` after a combo, in 1/seconds.
+    const DECAY: f32 = 1.0;
+
+    fn register_hit(&mut self) {
+        self.combo = if self.since_hit <= Self::COMBO_WINDOW {
+            self.combo + 1
+        } else {
+            1
+        };
+        self.since_hit = Duration::ZERO;
+        self.value = (self.value + Self::STEP).min(Self::MAX);
+    }
+}
+
+/// Why points were awarded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ScoreReason {
+    Hit,
+    /// Continuing a combo, on top of the points for the hit itself.
+    Combo,
+    Knockout,
+    RoundWin,
+    /// Collecting a score bonus.
+    Bonus,
+}
+
+impl ScoreReason {
+    /// Whether the [`ScoreMultiplier`] applies.
+    fn is_multiplied(self) -> bool {
+        matches!(
+            self,
+            ScoreReason::Hit | ScoreReason::Combo | ScoreReason::Knockout
+        )
+    }
+}
+
+/// Triggered on a player to award them points.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AwardPoints {
+    pub reason: ScoreReason,
+    /// The points before applying the [`ScoreMultiplier`].
+    pub points: u32,
+}
+
+/// Triggered after a player's score changed.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ScoreChanged {
+    pub player: Player,
+    pub score: u32,
+}
+
+/// The totals of the current or last match.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MatchResults {
+    pub players: Vec<PlayerResults>,
+    pub outcome: Option<RoundOutcome>,
+}
+
+impl MatchResults {
+    fn player(&mut self, player: Player) -> &mut PlayerResults {
+        let index = match self
+            .players
+            .iter()
+            .position(|results| results.player == player)
+        {
+            Some(index) => index,
+            None => {
+                self.players.push(PlayerResults::new(player));
+                self.players.len() - 1
+            }
+        };
+        &mut self.players[index]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct PlayerResults {
+    pub player: Player,
+    pub score: u32,
+    pub hits: u32,
+    pub knockouts: u32,
+    pub best_combo: u32,
+}
+
+impl PlayerResults {
+    fn new(player: Player) -> Self {
+        Self {
+            player,
+            score: 0,
+            hits: 0,
+            knockouts: 0,
+            best_combo: 0,
+        }
+    }
+}
+
+/// Points per point of damage dealt.
+const HIT_POINTS_PER_DAMAGE: f32 = 10.0;
+/// Points per hit of a combo after the first.
+const COMBO_POINTS: u32 = 50;
+const KNOCKOUT_POINTS: u32 = 1000;
+const ROUND_WIN_POINTS: u32 = 5000;
+
+fn reset_match_results(mut results: ResMut<MatchResults>) {
+    *results = MatchResults::default();
+}
+
+/// Every player shows up on the results screen, even without a single point.
+fn add_player_results(
+    trigger: Trigger<OnAdd, Player>,
+    mut results: ResMut<MatchResults>,
+    player_query: Query<&Player>,
+) {
+    if let Ok(player) = player_query.get(trigger.entity()) {
+        results.player(*player);
+    }
+}
+
+fn decay_multipliers(time: Res<Time>, mut multiplier_query: Query<&mut ScoreMultiplier>) {
+    for mut multiplier in &mut multiplier_query {
+        multiplier.since_hit += time.delta();
+        if multiplier.since_hit > ScoreMultiplier::COMBO_WINDOW {
+            multiplier.combo = 0;
+            let decay = ScoreMultiplier::DECAY * time.delta_seconds();
+            multiplier.value = (multiplier.value - decay).max(1.0);
+        }
+    }
+}
+
+fn score_hit(
+    trigger: Trigger<HitLanded>,
+    mut commands: Commands,
+    mut results: ResMut<MatchResults>,
+    mut attacker_query: Query<(&Player, &mut ScoreMultiplier)>,
+) {
+    let hit = *trigger.event();
+    if hit.blocked {
+        return;
+    }
+    let Ok((player, mut multiplier)) = attacker_query.get_mut(hit.attacker) else {
+        return;
+    };
+    multiplier.register_hit();
+
+    let results = results.player(*player);
+    results.hits += 1;
+    results.best_combo = results.best_combo.max(multiplier.combo);
+
+    let mut award = |reason, points| {
+        commands.trigger_targets(AwardPoints { reason, points }, hit.attacker);
+    };
+    award(
+        ScoreReason::Hit,
+        (hit.kind.damage() * HIT_POINTS_PER_DAMAGE) as u32,
+    );
+    if multiplier.combo > 1 {
+        award(ScoreReason::Combo, COMBO_POINTS * (multiplier.combo - 1));
+    }
+    if hit.knockout {
+        results.knockouts += 1;
+        award(ScoreReason::Knockout, KNOCKOUT_POINTS);
+    }
+}
+
+/// Award the round win to whoever won, and remember the outcome.
+fn score_round(
+    trigger: Trigger<RoundOver>,
+    mut commands: Commands,
+    mut results: ResMut<MatchResults>,
+    player_query: Query<(Entity, &Player, &StateMachine)>,
+) {
+    let outcome = trigger.event().0;
+    results.outcome = Some(outcome);
+    for (entity, player, machine) in &player_query {
+        let won = match outcome {
+            // Only those still standing share the win.
+            RoundOutcome::Cleared => machine.state() != CharacterState::Dead,
+            RoundOutcome::Won(winner) => winner == *player,
+            RoundOutcome::Defeated | RoundOutcome::Draw => false,
+        };
+        if won {
+            commands.trigger_targets(
+                AwardPoints {
+                    reason: ScoreReason::RoundWin,
+                    points: ROUND_WIN_POINTS,
+                },
+                entity,
+            );
+        }
+    }
+}
+
+fn award_points(
+    trigger: Trigger<AwardPoints>,
+    mut commands: Commands,
+    mut results: ResMut<MatchResults>,
+    mut score_query: Query<(&Player, &mut Score, Option<&ScoreMultiplier>)>,
+) {
+    let Ok((player, mut score, multiplier)) = score_query.get_mut(trigger.entity()) else {
+        return;
+    };
+    let AwardPoints { reason, points } = *trigger.event();
+    let points = match multiplier {
+        Some(multiplier) if reason.is_multiplied() => {
+            (points as f32 * multiplier.value).round() as u32
+        }
+        _ => points,
+    };
+    score.0 += points;
+    results.player(*player).score = score.0;
+    commands.trigger(ScoreChanged {
+        player: *player,
+        score: score.0,
+    });
+}
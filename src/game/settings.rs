@@ -0,0 +1,52 @@
+//! Player preferences, loaded at startup and saved whenever they change.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::storage::Storage;
+use crate::ui::localization::Language;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Settings>();
+    let settings = Settings::load(app.world().resource::<Storage>());
+    app.insert_resource(settings);
+    app.add_systems(
+        Update,
+        save_settings
+            .run_if(resource_changed::<Settings>.and_then(not(resource_added::<Settings>))),
+    );
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Settings {
+    pub language: Language,
+}
+
+impl Settings {
+    const STORAGE_KEY: &'static str = "settings";
+
+    fn load(storage: &Storage) -> Self {
+        let Some(data) = storage.read(Self::STORAGE_KEY) else {
+            return Self::default();
+        };
+        ron::from_str(&data).unwrap_or_else(|error| {
+            warn!("Discarding unreadable settings: {error}");
+            Self::default()
+        })
+    }
+}
+
+fn save_settings(settings: Res<Settings>, mut storage: ResMut<Storage>) {
+    let data = match ron::ser::to_string_pretty(&*settings, default()) {
+        Ok(data) => data,
+        Err(error) => {
+            error!("Failed to serialize settings: {error}");
+            return;
+        }
+    };
+    if let Err(error) = storage.write(Settings::STORAGE_KEY, &data) {
+        error!("Failed to save settings: {error}");
+    }
+}
@@ -0,0 +1,109 @@
+//! Spawn the grunts of the co-op mode.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, sprite::Anchor};
+
+use super::player::shadow;
+use crate::{
+    game::{
+        action::ActionController,
+        ai::{AiController, Difficulty},
+        animation::{PlayerAnimation, Tint},
+        assets::{ImageAsset, ImageAssets},
+        combat::Health,
+        combo::InputBuffer,
+        depth::{Elevation, YSort},
+        movement::{ConfineToLevel, Movement, MovementController},
+        state::{CharacterState, StateMachine},
+    },
+    screen::Screen,
+    AppSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(spawn_enemy);
+    app.register_type::<Enemy>();
+    app.add_systems(FixedUpdate, despawn_defeated_enemies.in_set(AppSet::Update));
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SpawnEnemy {
+    pub translation: Vec2,
+}
+
+/// A computer-controlled grunt that fights the players.
+/// Grunts don't fight each other.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Enemy;
+
+/// How long defeated grunts stay on the ground before disappearing.
+const DESPAWN_DELAY: Duration = Duration::from_millis(1500);
+
+fn spawn_enemy(
+    trigger: Trigger<SpawnEnemy>,
+    mut commands: Commands,
+    images: Res<ImageAssets>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 6, 2, Some(UVec2::splat(1)), None);
+    let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    let animation = PlayerAnimation::new();
+    let translation = trigger.event().translation;
+
+    commands
+        .spawn((
+            Name::new("Grunt"),
+            Enemy,
+            SpriteBundle {
+                texture: images[&ImageAsset::Ducky].clone_weak(),
+                transform: Transform::from_translation(translation.extend(0.0))
+                    .with_scale(Vec2::splat(8.0).extend(1.0)),
+                sprite: Sprite {
+                    // Face into the arena.
+                    flip_x: translation.x > 0.0,
+                    anchor: Anchor::BottomCenter,
+                    ..default()
+                },
+                ..default()
+            },
+            TextureAtlas {
+                layout: texture_atlas_layout,
+                index: animation.get_atlas_index(),
+            },
+            animation,
+            Tint(Color::srgb(0.6, 0.9, 0.6)),
+            (
+                MovementController::default(),
+                ActionController::default(),
+                InputBuffer::default(),
+                AiController::new(Difficulty::Easy),
+            ),
+            (
+                Movement { speed: 300.0 },
+                ConfineToLevel,
+                YSort,
+                Elevation::default(),
+            ),
+            Health::new(30.0),
+            StateMachine::default(),
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|children| {
+            children.spawn(shadow(&mut meshes, &mut materials));
+        });
+}
+
+fn despawn_defeated_enemies(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, &StateMachine), With<Enemy>>,
+) {
+    for (entity, machine) in &enemy_query {
+        if machine.state() == CharacterState::Dead && machine.elapsed() >= DESPAWN_DELAY {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1,18 +1,236 @@
 //! Spawn the main level by triggering other observers.
 
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
 
-use super::player::SpawnPlayer;
+use super::{
+    pickup::{PickupKind, SpawnPickup},
+    player::{Player, SpawnPlayer},
+};
+use crate::{
+    game::{
+        depth::{FAR_Z, NEAR_Z},
+        save::RestoreMatch,
+        waves::{GameMode, SpawnTrigger},
+    },
+    screen::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Level, LevelBounds, GroundPlane)>();
+    app.init_resource::<Level>();
+    app.init_resource::<LevelBounds>();
+    app.init_resource::<GroundPlane>();
+    // A resumed match brings its own level.
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        choose_level.run_if(not(resource_exists::<RestoreMatch>)),
+    );
     app.observe(spawn_level);
 }
 
 #[derive(Event, Debug)]
 pub struct SpawnLevel;
 
-fn spawn_level(_trigger: Trigger<SpawnLevel>, mut commands: Commands) {
-    // The only thing we have in our level is a player,
-    // but add things like walls etc. here.
-    commands.trigger(SpawnPlayer);
+/// Which level is played. Saved with the match, so resuming it spawns the same level.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum Level {
+    /// A stretch of street for two players to fight on.
+    #[default]
+    Arena,
+    /// The whole street, with grunts waiting along the way.
+    Street,
+}
+
+impl Level {
+    /// The level a new match of `mode` is played on.
+    pub fn for_mode(mode: GameMode) -> Self {
+        match mode {
+            GameMode::Versus => Level::Arena,
+            GameMode::Waves => Level::Street,
+        }
+    }
+}
+
+/// The playable area of the current level in world coordinates.
+/// Characters are kept inside it and the camera never shows anything outside of it.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect, Deref, DerefMut)]
+#[reflect(Resource)]
+pub struct LevelBounds(pub Rect);
+
+impl Default for LevelBounds {
+    fn default() -> Self {
+        Self(Rect::from_center_size(
+            Vec2::ZERO,
+            Vec2::new(3200.0, 1200.0),
+        ))
+    }
+}
+
+/// The band of the level characters can walk on, seen from the side.
+/// Lower y coordinates are closer to the viewer.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct GroundPlane {
+    /// The y coordinate of the front edge of the ground.
+    pub near: f32,
+    /// The y coordinate of the back edge of the ground.
+    pub far: f32,
+}
+
+impl Default for GroundPlane {
+    fn default() -> Self {
+        Self {
+            near: -450.0,
+            far: 50.0,
+        }
+    }
+}
+
+impl GroundPlane {
+    /// The z coordinate to draw something standing at `y` with,
+    /// between [`FAR_Z`] at the back and [`NEAR_Z`] at the front.
+    pub fn depth(&self, y: f32) -> f32 {
+        let t = ((y - self.far) / (self.near - self.far)).clamp(0.0, 1.0);
+        FAR_Z.lerp(NEAR_Z, t)
+    }
+
+    /// Clamp a y coordinate to the walkable band.
+    pub fn clamp(&self, y: f32) -> f32 {
+        y.clamp(self.near.min(self.far), self.near.max(self.far))
+    }
+}
+
+fn choose_level(mut level: ResMut<Level>, mode: Res<GameMode>) {
+    *level = Level::for_mode(*mode);
+}
+
+fn spawn_level(
+    _trigger: Trigger<SpawnLevel>,
+    mut commands: Commands,
+    level: Res<Level>,
+    bounds: Res<LevelBounds>,
+    ground: Res<GroundPlane>,
+) {
+    spawn_backdrop(&mut commands, &bounds, &ground);
+
+    match *level {
+        Level::Arena => {
+            commands.trigger(SpawnPlayer {
+                player: Player::Player1,
+                translation: Vec2::new(-300.0, -200.0),
+            });
+            commands.trigger(SpawnPlayer {
+                player: Player::Player2,
+                translation: Vec2::new(300.0, -200.0),
+            });
+            for (kind, x, y) in [
+                (PickupKind::Crate, 0.0, -100.0),
+                (PickupKind::Food, 0.0, -380.0),
+            ] {
+                commands.trigger(SpawnPickup {
+                    kind,
+                    translation: Vec2::new(x, y),
+                });
+            }
+        }
+        Level::Street => {
+            // Start at the left end and fight towards the right.
+            commands.trigger(SpawnPlayer {
+                player: Player::Player1,
+                translation: Vec2::new(-1300.0, -150.0),
+            });
+            commands.trigger(SpawnPlayer {
+                player: Player::Player2,
+                translation: Vec2::new(-1300.0, -300.0),
+            });
+            for trigger in [
+                SpawnTrigger {
+                    x: bounds.min.x,
+                    left: 0,
+                    right: 2,
+                },
+                SpawnTrigger {
+                    x: -200.0,
+                    left: 1,
+                    right: 2,
+                },
+                SpawnTrigger {
+                    x: 800.0,
+                    left: 2,
+                    right: 3,
+                },
+            ] {
+                commands.spawn((
+                    Name::new("Spawn Trigger"),
+                    trigger,
+                    StateScoped(Screen::Playing),
+                ));
+            }
+            for (kind, x, y) in [
+                (PickupKind::Crate, -900.0, -200.0),
+                (PickupKind::Food, 0.0, -320.0),
+                (PickupKind::Bottle, 400.0, -120.0),
+                (PickupKind::ScoreBonus, 600.0, -380.0),
+                (PickupKind::ExtraLife, 1300.0, -250.0),
+            ] {
+                commands.trigger(SpawnPickup {
+                    kind,
+                    translation: Vec2::new(x, y),
+                });
+            }
+        }
+    }
+}
+
+/// How far the brick wall reaches above the walkable ground, in world units.
+const WALL_HEIGHT: f32 = 256.0;
+/// How far the rooftops reach above the wall, in world units.
+const SKYLINE_HEIGHT: f32 = 128.0;
+
+/// Fill the level with a street: pavement to walk on, a brick wall behind it and the sky above.
+fn spawn_backdrop(commands: &mut Commands, bounds: &LevelBounds, ground: &GroundPlane) {
+    let back_edge = ground.near.max(ground.far);
+    let wall_top = back_edge + WALL_HEIGHT;
+    let skyline_top = wall_top + SKYLINE_HEIGHT;
+
+    commands
+        .spawn((
+            Name::new("Backdrop"),
+            SpatialBundle::default(),
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|children| {
+            for (name, color, bottom, top) in [
+                (
+                    "Pavement",
+                    Color::srgb(0.35, 0.35, 0.4),
+                    bounds.min.y,
+                    back_edge,
+                ),
+                ("Wall", Color::srgb(0.55, 0.25, 0.2), back_edge, wall_top),
+                ("Skyline", Color::srgb(0.2, 0.2, 0.3), wall_top, skyline_top),
+                (
+                    "Sky",
+                    Color::srgb(0.3, 0.45, 0.7),
+                    skyline_top,
+                    bounds.max.y,
+                ),
+            ] {
+                children.spawn((
+                    Name::new(name),
+                    SpriteBundle {
+                        // Behind everything that is sorted by depth.
+                        transform: Transform::from_xyz(bounds.min.x, bottom, 0.0),
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(Vec2::new(bounds.width(), (top - bottom).max(0.0))),
+                            anchor: Anchor::BottomLeft,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ));
+            }
+        });
 }
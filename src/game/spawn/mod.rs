@@ -4,10 +4,17 @@
 
 use bevy::prelude::*;
 
+pub mod enemy;
 pub mod level;
+pub mod pickup;
 pub mod player;
 pub mod ui;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((level::plugin, player::plugin, ui::plugin));
+    app.add_plugins((enemy::plugin, level::plugin, pickup::plugin, player::plugin));
+}
+
+/// The HUD, which needs rendering for its health bars.
+pub(super) fn presentation_plugin(app: &mut App) {
+    app.add_plugins(ui::plugin);
 }
@@ -0,0 +1,68 @@
+//! Spawn pickups on the ground.
+
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    game::{depth::YSort, pickup::Pickup},
+    screen::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(spawn_pickup);
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SpawnPickup {
+    pub kind: PickupKind,
+    pub translation: Vec2,
+}
+
+/// Everything that can be picked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum PickupKind {
+    /// Restores some health.
+    Food,
+    /// Grants another life, if the match has lives at all.
+    ExtraLife,
+    /// Adds points to the score.
+    ScoreBonus,
+    /// Carried and tossed. Heavy and big.
+    Crate,
+    /// Carried and tossed.
+    Bottle,
+}
+
+impl PickupKind {
+    /// How the item looks, standing on the ground.
+    /// We don't have item art yet, so these are plain colored shapes.
+    pub fn sprite(self) -> Sprite {
+        let (color, size) = match self {
+            PickupKind::Food => (Color::srgb(0.9, 0.3, 0.2), Vec2::new(60.0, 40.0)),
+            PickupKind::ExtraLife => (Color::srgb(0.3, 0.9, 0.3), Vec2::splat(50.0)),
+            PickupKind::ScoreBonus => (Color::srgb(1.0, 0.85, 0.2), Vec2::splat(40.0)),
+            PickupKind::Crate => (Color::srgb(0.55, 0.35, 0.15), Vec2::splat(90.0)),
+            PickupKind::Bottle => (Color::srgb(0.2, 0.6, 0.4), Vec2::new(25.0, 70.0)),
+        };
+        Sprite {
+            color,
+            custom_size: Some(size),
+            anchor: Anchor::BottomCenter,
+            ..default()
+        }
+    }
+}
+
+fn spawn_pickup(trigger: Trigger<SpawnPickup>, mut commands: Commands) {
+    let SpawnPickup { kind, translation } = *trigger.event();
+    commands.spawn((
+        Name::new("Pickup"),
+        Pickup(kind),
+        SpriteBundle {
+            sprite: kind.sprite(),
+            transform: Transform::from_translation(translation.extend(0.0)),
+            ..default()
+        },
+        YSort,
+        StateScoped(Screen::Playing),
+    ));
+}
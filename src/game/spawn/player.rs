@@ -3,15 +3,27 @@
 use bevy::{
     color::palettes::css::{BLUE, RED},
     prelude::*,
+    sprite::{Anchor, MaterialMesh2dBundle},
 };
 
 use crate::{
     game::{
+        action::ActionController,
+        ai::{AiController, Difficulty, Opponent},
         animation::PlayerAnimation,
         assets::{ImageAsset, ImageAssets},
-        movement::{Movement, MovementController, WrapWithinWindow},
+        combat::Health,
+        combo::InputBuffer,
+        depth::{Elevation, YSort},
+        jump::{Jump, Shadow},
+        lives::Lives,
+        movement::{ConfineToLevel, KeyBindings, Movement, MovementController},
+        score::{Score, ScoreMultiplier},
+        state::StateMachine,
+        waves::GameMode,
     },
     screen::Screen,
+    ui::localization::Localized,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -19,8 +31,14 @@ pub(super) fn plugin(app: &mut App) {
     app.register_type::<Player>();
 }
 
-#[derive(Event, Debug)]
-pub struct SpawnPlayer;
+/// How many extra lives players start with when they have any.
+const PLAYER_LIVES: u32 = 3;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SpawnPlayer {
+    pub player: Player,
+    pub translation: Vec2,
+}
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Component)]
@@ -30,6 +48,14 @@ pub enum Player {
 }
 
 impl Player {
+    /// A number from `0` for the first player on.
+    pub fn index(&self) -> usize {
+        match self {
+            Player::Player1 => 0,
+            Player::Player2 => 1,
+        }
+    }
+
     pub fn color(&self) -> Color {
         match self {
             Player::Player1 => BLUE.into(),
@@ -43,13 +69,40 @@ impl Player {
             Player::Player2 => "Player 2",
         }
     }
+
+    /// What players see this player called.
+    pub fn name(&self) -> Localized {
+        Localized::new(match self {
+            Player::Player1 => "player-1",
+            Player::Player2 => "player-2",
+        })
+    }
+
+    /// The name of the character this player fights as.
+    pub fn character(&self) -> &'static str {
+        // Everybody is a duck for now.
+        "Ducky"
+    }
+
+    /// The keys this player uses on a shared keyboard.
+    pub fn key_bindings(&self) -> KeyBindings {
+        match self {
+            Player::Player1 => KeyBindings::WASD,
+            Player::Player2 => KeyBindings::ARROWS,
+        }
+    }
 }
 
 fn spawn_player(
-    _trigger: Trigger<SpawnPlayer>,
+    trigger: Trigger<SpawnPlayer>,
     mut commands: Commands,
     images: Res<ImageAssets>,
+    opponent: Res<Opponent>,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     // A texture atlas is a way to split one image with a grid into multiple sprites.
     // By attaching it to a [`SpriteBundle`] and providing an index, we can specify which section of the image we want to see.
@@ -58,23 +111,87 @@ fn spawn_player(
     let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 6, 2, Some(UVec2::splat(1)), None);
     let texture_atlas_layout = texture_atlas_layouts.add(layout);
     let player_animation = PlayerAnimation::new();
+    let SpawnPlayer {
+        player,
+        translation,
+    } = *trigger.event();
 
-    commands.spawn((
+    let mut entity = commands.spawn((
         Name::new("Player"),
-        Player::Player1,
+        player,
         SpriteBundle {
             texture: images[&ImageAsset::Ducky].clone_weak(),
-            transform: Transform::from_scale(Vec2::splat(8.0).extend(1.0)),
+            transform: Transform::from_translation(translation.extend(0.0))
+                .with_scale(Vec2::splat(8.0).extend(1.0)),
+            sprite: Sprite {
+                // Face the opponent.
+                flip_x: translation.x > 0.0,
+                // The transform is where the feet touch the ground.
+                anchor: Anchor::BottomCenter,
+                ..default()
+            },
             ..Default::default()
         },
         TextureAtlas {
             layout: texture_atlas_layout.clone(),
             index: player_animation.get_atlas_index(),
         },
-        MovementController::default(),
-        Movement { speed: 420.0 },
-        WrapWithinWindow,
         player_animation,
+        (
+            MovementController::default(),
+            ActionController::default(),
+            InputBuffer::default(),
+        ),
+        (
+            Movement { speed: 420.0 },
+            Jump { speed: 1100.0 },
+            ConfineToLevel,
+            YSort,
+            Elevation::default(),
+        ),
+        Health::new(100.0),
+        (Score::default(), ScoreMultiplier::default()),
+        StateMachine::default(),
         StateScoped(Screen::Playing),
     ));
+
+    // Only the co-op mode gives second chances.
+    if *mode == GameMode::Waves {
+        entity.insert(Lives(PLAYER_LIVES));
+    }
+
+    // Either a human or the AI is in control.
+    match (player, *opponent) {
+        (Player::Player2, Opponent::Cpu) => {
+            entity.insert(AiController::new(*difficulty));
+        }
+        _ => {
+            entity.insert(player.key_bindings());
+        }
+    }
+
+    entity.with_children(|children| {
+        children.spawn(shadow(&mut meshes, &mut materials));
+    });
+}
+
+/// A shadow to spawn as a child of a character.
+pub(super) fn shadow(
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) -> impl Bundle {
+    // Sizes are in texels, since characters are scaled up.
+    (
+        Name::new("Shadow"),
+        MaterialMesh2dBundle {
+            mesh: meshes.add(Ellipse::new(10.0, 3.0)).into(),
+            material: materials.add(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+            // Draw behind the character.
+            transform: Transform::from_xyz(0.0, 0.0, -0.5),
+            ..default()
+        },
+        Shadow {
+            half_size_elevation: 300.0,
+        },
+    )
 }
@@ -3,18 +3,44 @@ use bevy::prelude::*;
 use bevy::render::render_resource::{AsBindGroup, ShaderRef};
 use bevy::ui::Val::*;
 
-use crate::game::assets::UiAssets;
-use crate::{screen::Screen, ui::widgets::Containers};
+use crate::game::{
+    assets::{FontAsset, FontAssets, UiAssets},
+    combat::Health,
+    lives::Lives,
+    score::ScoreChanged,
+    waves::{GameMode, WaveProgress},
+};
+use crate::{
+    screen::Screen,
+    ui::{
+        localization::{Localized, LocalizedText},
+        widgets::Containers,
+    },
+    AppSet,
+};
 
 use super::player::Player;
 
 /// This example uses a shader source file from the assets subdirectory
 const HEALTH_BAR_UI_SHADER_PATH: &str = "shaders/health_bar_ui.wgsl";
 
+/// Twice the pixel font's size, so that its pixels stay square.
+const HUD_FONT_SIZE: f32 = 32.0;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(UiMaterialPlugin::<HealthBarUiMaterial>::default());
     app.observe(spawn_game_ui);
+    app.observe(update_score_ui);
     app.register_type::<GameUi>();
+    app.add_systems(
+        Update,
+        (
+            update_health_bar_ui,
+            update_lives_ui,
+            update_wave_ui.run_if(resource_changed::<WaveProgress>),
+        )
+            .in_set(AppSet::Update),
+    );
 }
 
 #[derive(Event, Debug)]
@@ -24,16 +50,42 @@ pub struct SpawnGameUi;
 #[reflect(Component)]
 pub struct GameUi;
 
-fn spawn_game_ui(_trigger: Trigger<SpawnGameUi>, mut commands: Commands, ui_assets: Res<UiAssets>) {
+fn spawn_game_ui(
+    _trigger: Trigger<SpawnGameUi>,
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    fonts: Res<FontAssets>,
+    mode: Res<GameMode>,
+    mut health_bar_materials: ResMut<Assets<HealthBarUiMaterial>>,
+) {
+    // Every player gets their own copy of the material, so their health bars can differ.
+    let Some(health_bar) = health_bar_materials.get(&ui_assets.health_bar).cloned() else {
+        return;
+    };
+    let materials = [
+        health_bar_materials.add(health_bar.clone()),
+        health_bar_materials.add(health_bar),
+    ];
+    let text_style = TextStyle {
+        font: fonts[&FontAsset::Pixel].clone_weak(),
+        font_size: HUD_FONT_SIZE,
+        color: WHITE.into(),
+    };
+
     commands
         .ui_root()
         .insert(StateScoped(Screen::Playing))
         .with_children(|parent| {
-            top_ui_root(parent, &ui_assets.health_bar);
+            top_ui_root(parent, materials, *mode, &text_style);
         });
 }
 
-fn top_ui_root(parent: &mut ChildBuilder, material: &Handle<HealthBarUiMaterial>) {
+fn top_ui_root(
+    parent: &mut ChildBuilder,
+    [player1, player2]: [Handle<HealthBarUiMaterial>; 2],
+    mode: GameMode,
+    text_style: &TextStyle,
+) {
     parent
         .spawn((
             Name::new("Top Game UI"),
@@ -51,12 +103,35 @@ fn top_ui_root(parent: &mut ChildBuilder, material: &Handle<HealthBarUiMaterial>
             },
         ))
         .with_children(|parent| {
-            player_ui(parent, Player::Player1, material.clone_weak());
-            player_ui(parent, Player::Player2, material.clone_weak());
+            player_ui(parent, Player::Player1, player1, text_style);
+            if mode == GameMode::Waves {
+                wave_ui(parent, text_style);
+            }
+            player_ui(parent, Player::Player2, player2, text_style);
         });
 }
 
-fn player_ui(parent: &mut ChildBuilder, player: Player, material: Handle<HealthBarUiMaterial>) {
+#[derive(Component)]
+pub struct WaveUi;
+
+fn wave_ui(parent: &mut ChildBuilder, text_style: &TextStyle) {
+    parent.spawn((
+        Name::new("Wave UI Text"),
+        TextBundle::from_section("", text_style.clone()).with_style(Style {
+            align_self: AlignSelf::Center,
+            ..default()
+        }),
+        LocalizedText::new(Localized::new("wave")),
+        WaveUi,
+    ));
+}
+
+fn player_ui(
+    parent: &mut ChildBuilder,
+    player: Player,
+    material: Handle<HealthBarUiMaterial>,
+    text_style: &TextStyle,
+) {
     parent
         .spawn((
             Name::new(player.id()),
@@ -74,12 +149,12 @@ fn player_ui(parent: &mut ChildBuilder, player: Player, material: Handle<HealthB
             },
         ))
         .with_children(|parent| {
-            name_score_ui(parent, player);
-            lives_health_ui(parent, material, player);
+            name_score_ui(parent, player, text_style);
+            lives_health_ui(parent, material, player, text_style);
         });
 }
 
-fn name_score_ui(parent: &mut ChildBuilder, player: Player) {
+fn name_score_ui(parent: &mut ChildBuilder, player: Player, text_style: &TextStyle) {
     parent
         .spawn((
             Name::new("Name Score UI"),
@@ -97,15 +172,15 @@ fn name_score_ui(parent: &mut ChildBuilder, player: Player) {
             },
         ))
         .with_children(|parent| {
-            name_ui(parent, player);
-            score_ui(parent, player);
+            name_ui(parent, player, text_style);
+            score_ui(parent, player, text_style);
         });
 }
 
 #[derive(Component)]
 pub struct NameUi;
 
-fn name_ui(parent: &mut ChildBuilder, player: Player) {
+fn name_ui(parent: &mut ChildBuilder, player: Player, text_style: &TextStyle) {
     parent
         .spawn((
             Name::new("Name UI"),
@@ -123,14 +198,8 @@ fn name_ui(parent: &mut ChildBuilder, player: Player) {
         .with_children(|parent| {
             parent.spawn((
                 Name::new("Name UI Text"),
-                TextBundle::from_section(
-                    player.id(),
-                    TextStyle {
-                        font_size: 30.0,
-                        color: WHITE.into(),
-                        ..default()
-                    },
-                ),
+                TextBundle::from_section("", text_style.clone()),
+                LocalizedText::new(player.name()),
                 player,
                 NameUi,
             ));
@@ -140,7 +209,7 @@ fn name_ui(parent: &mut ChildBuilder, player: Player) {
 #[derive(Component)]
 pub struct ScoreUi;
 
-fn score_ui(parent: &mut ChildBuilder, player: Player) {
+fn score_ui(parent: &mut ChildBuilder, player: Player, text_style: &TextStyle) {
     parent
         .spawn((
             Name::new("Score UI"),
@@ -158,14 +227,7 @@ fn score_ui(parent: &mut ChildBuilder, player: Player) {
         .with_children(|parent| {
             parent.spawn((
                 Name::new("Score UI Text"),
-                TextBundle::from_section(
-                    "0",
-                    TextStyle {
-                        font_size: 30.0,
-                        color: WHITE.into(),
-                        ..default()
-                    },
-                ),
+                TextBundle::from_section("0", text_style.clone()),
                 player,
                 ScoreUi,
             ));
@@ -176,6 +238,7 @@ fn lives_health_ui(
     parent: &mut ChildBuilder,
     material: Handle<HealthBarUiMaterial>,
     player: Player,
+    text_style: &TextStyle,
 ) {
     parent
         .spawn((
@@ -194,7 +257,7 @@ fn lives_health_ui(
             },
         ))
         .with_children(|parent| {
-            lives_ui(parent, player);
+            lives_ui(parent, player, text_style);
             health_ui(parent, material, player);
         });
 }
@@ -202,7 +265,7 @@ fn lives_health_ui(
 #[derive(Component)]
 pub struct LivesUi;
 
-fn lives_ui(parent: &mut ChildBuilder, player: Player) {
+fn lives_ui(parent: &mut ChildBuilder, player: Player, text_style: &TextStyle) {
     parent
         .spawn((
             Name::new("Lives UI"),
@@ -220,14 +283,7 @@ fn lives_ui(parent: &mut ChildBuilder, player: Player) {
         .with_children(|parent| {
             parent.spawn((
                 Name::new("Lives UI Text"),
-                TextBundle::from_section(
-                    "0",
-                    TextStyle {
-                        font_size: 30.0,
-                        color: WHITE.into(),
-                        ..default()
-                    },
-                ),
+                TextBundle::from_section("0", text_style.clone()),
                 player,
                 LivesUi,
             ));
@@ -270,6 +326,59 @@ fn health_ui(parent: &mut ChildBuilder, material: Handle<HealthBarUiMaterial>, p
         });
 }
 
+fn update_health_bar_ui(
+    player_query: Query<(&Player, &Health), Changed<Health>>,
+    health_bar_query: Query<(&Player, &Handle<HealthBarUiMaterial>), With<HealthBarUi>>,
+    mut health_bar_materials: ResMut<Assets<HealthBarUiMaterial>>,
+) {
+    for (player, health) in &player_query {
+        for (bar_player, handle) in &health_bar_query {
+            if bar_player != player {
+                continue;
+            }
+            if let Some(material) = health_bar_materials.get_mut(handle) {
+                material.slider = health.fraction();
+            }
+        }
+    }
+}
+
+fn update_lives_ui(
+    player_query: Query<(&Player, &Lives), Changed<Lives>>,
+    mut lives_query: Query<(&Player, &mut Text), With<LivesUi>>,
+) {
+    for (player, lives) in &player_query {
+        for (text_player, mut text) in &mut lives_query {
+            if text_player == player {
+                text.sections[0].value = lives.0.to_string();
+            }
+        }
+    }
+}
+
+fn update_score_ui(
+    trigger: Trigger<ScoreChanged>,
+    mut score_query: Query<(&Player, &mut Text), With<ScoreUi>>,
+) {
+    let ScoreChanged { player, score } = *trigger.event();
+    for (text_player, mut text) in &mut score_query {
+        if *text_player == player {
+            text.sections[0].value = score.to_string();
+        }
+    }
+}
+
+fn update_wave_ui(
+    progress: Res<WaveProgress>,
+    mut wave_query: Query<&mut LocalizedText, With<WaveUi>>,
+) {
+    for mut text in &mut wave_query {
+        text.text = Localized::new("wave")
+            .with_arg("current", progress.current.to_string())
+            .with_arg("total", progress.total.to_string());
+    }
+}
+
 #[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
 pub struct HealthBarUiMaterial {
     /// Represents how much of the image is visible
@@ -0,0 +1,308 @@
+//! The character state machine.
+//! Every character is in exactly one [`CharacterState`] at a time. Transitions only happen here
+//! or through [`StateMachine::transition`], which enforces the rules between states.
+//! Movement, combat and animation read the current state instead of inferring it themselves.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    action::ActionController,
+    combat::{AttackKind, Health},
+    combo::{InputBuffer, MoveList},
+    jump::{Airborne, Landed},
+    movement::MovementController,
+    pickup::HeldItem,
+};
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<StateMachine>();
+    app.add_systems(
+        FixedUpdate,
+        (
+            tick_state_machines.in_set(AppSet::TickTimers),
+            (drive_state_machines, announce_state_changes)
+                .chain()
+                .in_set(AppSet::ChangeState),
+        ),
+    );
+    app.observe(land);
+}
+
+/// Everything a character can be doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CharacterState {
+    Idle,
+    Walk,
+    Run,
+    /// In the air, either jumping or falling.
+    Jump,
+    Attack(AttackKind),
+    Block,
+    /// Holding on to another character, see [`Grabbing`](super::grab::Grabbing).
+    Grab,
+    /// Being held by another character.
+    Grabbed,
+    /// Throwing the grabbed character.
+    Throw,
+    /// Reeling from a hit, unable to act.
+    HitStun,
+    /// Lying on the ground after a heavy hit.
+    KnockedDown,
+    GettingUp,
+    /// Out of health. There is no way back from this state.
+    Dead,
+}
+
+impl CharacterState {
+    /// How long the state lasts before it ends on its own.
+    /// `None` means the state lasts until something else changes it.
+    pub fn duration(self) -> Option<Duration> {
+        match self {
+            CharacterState::Attack(kind) => Some(kind.duration()),
+            CharacterState::Grab => Some(Duration::from_millis(1500)),
+            CharacterState::Throw => Some(Duration::from_millis(400)),
+            CharacterState::HitStun => Some(Duration::from_millis(300)),
+            CharacterState::KnockedDown => Some(Duration::from_millis(1000)),
+            CharacterState::GettingUp => Some(Duration::from_millis(500)),
+            _ => None,
+        }
+    }
+
+    /// Whether the character is in control: it can be steered by its [`MovementController`]
+    /// and start jumps, attacks, blocks and grabs.
+    pub fn can_act(self) -> bool {
+        matches!(
+            self,
+            CharacterState::Idle
+                | CharacterState::Walk
+                | CharacterState::Run
+                | CharacterState::Jump
+        )
+    }
+
+    /// Whether attacks can hit the character.
+    pub fn is_vulnerable(self) -> bool {
+        !matches!(
+            self,
+            CharacterState::KnockedDown | CharacterState::GettingUp | CharacterState::Dead
+        )
+    }
+
+    /// The transition rules between states.
+    pub fn can_transition_to(self, next: CharacterState) -> bool {
+        use CharacterState::*;
+        match (self, next) {
+            (Dead, _) => false,
+            // Being hit or dying interrupts anything else.
+            (_, HitStun | KnockedDown | Dead) => true,
+            (Idle | Walk | Run, Idle | Walk | Run | Jump | Attack(_) | Block | Grab) => true,
+            // Grabs beat blocks.
+            (Idle | Walk | Run | Block, Grabbed) => true,
+            (Jump, Idle | Attack(_)) => true,
+            (Attack(_), Idle | Jump) => true,
+            // Combos cancel one attack into the next.
+            (Attack(_), Attack(_)) => true,
+            (Block, Idle) => true,
+            (Grab, Idle | Throw) => true,
+            (Grabbed, Idle) => true,
+            (Throw, Idle) => true,
+            (HitStun, Idle | Jump) => true,
+            (KnockedDown, GettingUp) => true,
+            (GettingUp, Idle) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Tracks a character's [`CharacterState`] and how long it has been in it.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct StateMachine {
+    state: CharacterState,
+    /// The state as of the last [`StateEntered`] event.
+    announced: CharacterState,
+    elapsed: Duration,
+}
+
+impl Default for StateMachine {
+    fn default() -> Self {
+        Self {
+            state: CharacterState::Idle,
+            announced: CharacterState::Idle,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl StateMachine {
+    pub fn state(&self) -> CharacterState {
+        self.state
+    }
+
+    /// How long the character has been in the current state.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Whether a timed state has run its course.
+    pub fn finished(&self) -> bool {
+        self.state
+            .duration()
+            .is_some_and(|duration| self.elapsed >= duration)
+    }
+
+    /// Move to `next` if the rules allow it. Returns whether the state changed.
+    pub fn transition(&mut self, next: CharacterState) -> bool {
+        if self.state == next || !self.state.can_transition_to(next) {
+            return false;
+        }
+        self.state = next;
+        self.elapsed = Duration::ZERO;
+        true
+    }
+}
+
+/// Triggered on a character after it entered a new state.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct StateEntered(pub CharacterState);
+
+fn tick_state_machines(time: Res<Time>, mut machine_query: Query<&mut StateMachine>) {
+    for mut machine in &mut machine_query {
+        machine.elapsed += time.delta();
+    }
+}
+
+/// Move characters between states based on their input and timers.
+fn drive_state_machines(
+    moves: Res<MoveList>,
+    mut machine_query: Query<(
+        &mut StateMachine,
+        &MovementController,
+        &ActionController,
+        Has<Airborne>,
+        Option<&Health>,
+        Option<&mut InputBuffer>,
+        Has<HeldItem>,
+    )>,
+) {
+    for (mut machine, movement, action, airborne, health, mut buffer, holding) in &mut machine_query
+    {
+        // Timed states end on their own.
+        if machine.finished() {
+            let next = match machine.state() {
+                CharacterState::KnockedDown
+                    if health.is_some_and(|health| health.current <= 0.0) =>
+                {
+                    CharacterState::Dead
+                }
+                CharacterState::KnockedDown => CharacterState::GettingUp,
+                _ if airborne => CharacterState::Jump,
+                _ => CharacterState::Idle,
+            };
+            machine.transition(next);
+        }
+
+        let state = machine.state();
+
+        // Chain into the next attack of a combo.
+        if let (CharacterState::Attack(current), Some(buffer)) = (state, buffer.as_deref_mut()) {
+            if let Some(next) = moves.chain(buffer, current, machine.elapsed()) {
+                machine.transition(CharacterState::Attack(next));
+                buffer.clear();
+                continue;
+            }
+        }
+
+        if state.can_act() {
+            // Buffered attacks come out as soon as the character can act again.
+            let attack = buffer
+                .as_deref()
+                .map_or(action.attack, InputBuffer::attack_pending);
+            if attack {
+                // Attacking with an item in hand tosses it instead.
+                let kind = if holding && !airborne {
+                    AttackKind::Toss
+                } else {
+                    buffer
+                        .as_deref()
+                        .filter(|_| !airborne)
+                        .and_then(|buffer| moves.start(buffer))
+                        .unwrap_or_else(|| AttackKind::choose(airborne, movement.0))
+                };
+                machine.transition(CharacterState::Attack(kind));
+                if let Some(buffer) = buffer.as_deref_mut() {
+                    buffer.clear();
+                }
+                continue;
+            }
+            if action.jump && !airborne {
+                machine.transition(CharacterState::Jump);
+                continue;
+            }
+            if action.block && !airborne {
+                machine.transition(CharacterState::Block);
+                continue;
+            }
+        }
+
+        match state {
+            CharacterState::Block if !action.block => {
+                machine.transition(CharacterState::Idle);
+            }
+            CharacterState::Grab if action.attack => {
+                machine.transition(CharacterState::Throw);
+                if let Some(buffer) = buffer.as_deref_mut() {
+                    buffer.clear();
+                }
+            }
+            _ => {}
+        }
+
+        if matches!(
+            state,
+            CharacterState::Idle | CharacterState::Walk | CharacterState::Run
+        ) {
+            let next = if movement.0 == Vec2::ZERO {
+                CharacterState::Idle
+            } else if action.run {
+                CharacterState::Run
+            } else {
+                CharacterState::Walk
+            };
+            machine.transition(next);
+        }
+    }
+}
+
+/// Trigger [`StateEntered`] for every character whose state changed,
+/// no matter whether that happened here or in an observer.
+fn announce_state_changes(
+    mut commands: Commands,
+    mut machine_query: Query<(Entity, &mut StateMachine)>,
+) {
+    for (entity, mut machine) in &mut machine_query {
+        if machine.announced == machine.state {
+            continue;
+        }
+        machine.bypass_change_detection().announced = machine.state;
+        commands.trigger_targets(StateEntered(machine.state), entity);
+    }
+}
+
+/// Landing ends jumps and aerial attacks.
+fn land(trigger: Trigger<Landed>, mut machine_query: Query<&mut StateMachine>) {
+    let Ok(mut machine) = machine_query.get_mut(trigger.entity()) else {
+        return;
+    };
+    let landed = match machine.state() {
+        CharacterState::Jump => true,
+        CharacterState::Attack(kind) => kind.is_aerial(),
+        _ => false,
+    };
+    if landed {
+        machine.transition(CharacterState::Idle);
+    }
+}
@@ -0,0 +1,118 @@
+//! Persisting small pieces of data between sessions.
+//! Native builds write files into the platform's data directory,
+//! while web builds use the browser's local storage.
+//! Running headless keeps everything in memory instead, so that tests leave the player's data
+//! and each other alone.
+
+use bevy::{prelude::*, utils::HashMap};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Storage>();
+}
+
+/// Used to keep our data apart from other applications'.
+const APP_NAME: &str = "bevy_quickstart";
+
+/// Where data is kept between sessions.
+#[derive(Resource, Debug, Default)]
+pub struct Storage {
+    /// Replaces the platform's storage when present.
+    memory: Option<HashMap<String, String>>,
+}
+
+impl Storage {
+    /// Storage that starts out empty and only lasts as long as the app.
+    #[cfg(feature = "headless")]
+    pub fn in_memory() -> Self {
+        Self {
+            memory: Some(HashMap::new()),
+        }
+    }
+
+    /// Read the data stored under `key`, if there is any.
+    pub fn read(&self, key: &str) -> Option<String> {
+        match &self.memory {
+            Some(memory) => memory.get(key).cloned(),
+            None => platform::read(key),
+        }
+    }
+
+    /// Store `data` under `key`, replacing what was there before.
+    pub fn write(&mut self, key: &str, data: &str) -> Result<(), String> {
+        match &mut self.memory {
+            Some(memory) => {
+                memory.insert(key.to_string(), data.to_string());
+                Ok(())
+            }
+            None => platform::write(key, data),
+        }
+    }
+
+    /// Forget the data stored under `key`.
+    pub fn remove(&mut self, key: &str) {
+        match &mut self.memory {
+            Some(memory) => {
+                memory.remove(key);
+            }
+            None => platform::remove(key),
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod platform {
+    use std::{fs, path::PathBuf};
+
+    use super::APP_NAME;
+
+    fn path(key: &str) -> Option<PathBuf> {
+        Some(dirs::data_dir()?.join(APP_NAME).join(format!("{key}.ron")))
+    }
+
+    pub(super) fn read(key: &str) -> Option<String> {
+        fs::read_to_string(path(key)?).ok()
+    }
+
+    pub(super) fn write(key: &str, data: &str) -> Result<(), String> {
+        let path = path(key).ok_or("no data directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+        fs::write(path, data).map_err(|error| error.to_string())
+    }
+
+    pub(super) fn remove(key: &str) {
+        if let Some(path) = path(key) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod platform {
+    use super::APP_NAME;
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    pub(super) fn read(key: &str) -> Option<String> {
+        local_storage()?
+            .get_item(&format!("{APP_NAME}.{key}"))
+            .ok()
+            .flatten()
+    }
+
+    pub(super) fn write(key: &str, data: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or("no local storage")?
+            .set_item(&format!("{APP_NAME}.{key}"), data)
+            .map_err(|error| format!("{error:?}"))
+    }
+
+    pub(super) fn remove(key: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(&format!("{APP_NAME}.{key}"));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! The co-op mode, where both players fight waves of grunts together.
+//! Levels place [`SpawnTrigger`]s along the arena. Once the previous wave is cleared,
+//! walking past the next trigger sends in its grunts from the edges of the players' view.
+//! The round is won when every wave is cleared and lost when both players are out of lives.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    lives::Lives,
+    rng::{GameRng, RngStream},
+    round::{is_out, RoundEnd, RoundOutcome, RoundOver},
+    spawn::{
+        enemy::{Enemy, SpawnEnemy},
+        level::{GroundPlane, LevelBounds},
+        player::Player,
+    },
+    state::{CharacterState, StateMachine},
+};
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(GameMode, WaveProgress, SpawnTrigger)>();
+    app.init_resource::<GameMode>();
+    app.init_resource::<WaveProgress>();
+    app.add_systems(OnEnter(Screen::Playing), reset_wave_progress);
+    app.add_systems(
+        FixedUpdate,
+        (fire_spawn_triggers, check_round_over)
+            .chain()
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing).and_then(resource_equals(GameMode::Waves))),
+    );
+}
+
+/// Which kind of match is played.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum GameMode {
+    /// The players fight each other.
+    #[default]
+    Versus,
+    /// The players fight waves of grunts together.
+    Waves,
+}
+
+/// Sends in a wave of grunts once a player walks past it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct SpawnTrigger {
+    /// The x coordinate a player has to reach.
+    pub x: f32,
+    /// How many grunts enter from the left edge of the screen.
+    pub left: u32,
+    /// How many grunts enter from the right edge of the screen.
+    pub right: u32,
+}
+
+/// How far the players have come in the current round.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct WaveProgress {
+    /// The number of waves sent in so far.
+    pub current: usize,
+    /// The number of waves in the level.
+    pub total: usize,
+}
+
+/// How far outside the players' view grunts start, in world units.
+const SPAWN_MARGIN: f32 = 100.0;
+
+/// Half the width the camera shows while zoomed in all the way, in world units.
+/// Grunts enter from the edges of this view around the players rather than from the camera's,
+/// which moves with the frame rate and shakes, so that replays send them in at the same spots.
+const HALF_VIEW_WIDTH: f32 = 960.0;
+
+fn reset_wave_progress(mut progress: ResMut<WaveProgress>) {
+    *progress = WaveProgress::default();
+}
+
+fn fire_spawn_triggers(
+    mut commands: Commands,
+    mut progress: ResMut<WaveProgress>,
+    bounds: Res<LevelBounds>,
+    ground: Res<GroundPlane>,
+    trigger_query: Query<(Entity, &SpawnTrigger)>,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<&StateMachine, With<Enemy>>,
+    mut rng: ResMut<GameRng>,
+) {
+    // Send in waves one at a time.
+    if enemy_query
+        .iter()
+        .any(|machine| machine.state() != CharacterState::Dead)
+    {
+        return;
+    }
+    // Levels are spawned through commands, so the triggers may only just have appeared.
+    let total = progress.current + trigger_query.iter().count();
+    if progress.total != total {
+        progress.total = total;
+    }
+
+    let Some((entity, trigger)) = trigger_query
+        .iter()
+        .min_by(|(_, a), (_, b)| a.x.total_cmp(&b.x))
+    else {
+        return;
+    };
+    let reached = player_query
+        .iter()
+        .any(|transform| transform.translation.x >= trigger.x);
+    if !reached {
+        return;
+    }
+    commands.entity(entity).despawn_recursive();
+    progress.current += 1;
+
+    // Enter from just outside the view, which is kept inside the level like the camera's,
+    // but never from outside the level.
+    let (min, max) = player_query
+        .iter()
+        .map(|transform| transform.translation.x)
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), x| {
+            (min.min(x), max.max(x))
+        });
+    let center = if bounds.width() > 2.0 * HALF_VIEW_WIDTH {
+        ((min + max) / 2.0).clamp(
+            bounds.min.x + HALF_VIEW_WIDTH,
+            bounds.max.x - HALF_VIEW_WIDTH,
+        )
+    } else {
+        bounds.center().x
+    };
+    let half_width = HALF_VIEW_WIDTH + SPAWN_MARGIN;
+    let left = (center - half_width).max(bounds.min.x);
+    let right = (center + half_width).min(bounds.max.x);
+
+    let rng = rng.stream(RngStream::Gameplay);
+    let (near, far) = (ground.near.min(ground.far), ground.near.max(ground.far));
+    for (x, count) in [(left, trigger.left), (right, trigger.right)] {
+        for _ in 0..count {
+            commands.trigger(SpawnEnemy {
+                translation: Vec2::new(x, rng.gen_range(near..=far)),
+            });
+        }
+    }
+}
+
+fn check_round_over(
+    mut commands: Commands,
+    progress: Res<WaveProgress>,
+    round_end: Option<Res<RoundEnd>>,
+    trigger_query: Query<(), With<SpawnTrigger>>,
+    enemy_query: Query<&StateMachine, With<Enemy>>,
+    player_query: Query<(&StateMachine, Option<&Lives>), With<Player>>,
+) {
+    if round_end.is_some() {
+        return;
+    }
+
+    let defeated = !player_query.is_empty()
+        && player_query
+            .iter()
+            .all(|(machine, lives)| is_out(machine, lives));
+    let cleared = progress.current > 0
+        && trigger_query.is_empty()
+        && enemy_query
+            .iter()
+            .all(|machine| machine.state() == CharacterState::Dead);
+
+    let outcome = if defeated {
+        RoundOutcome::Defeated
+    } else if cleared {
+        RoundOutcome::Cleared
+    } else {
+        return;
+    };
+    commands.trigger(RoundOver(outcome));
+}
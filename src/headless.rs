@@ -0,0 +1,388 @@
+//! Running the game without a window, audio or rendering, e.g. in `cargo test` on machines
+//! without a GPU. [`HeadlessPlugin`] runs the [`SimulationPlugin`] on [`MinimalPlugins`].
+//! [`Simulation`] drives a match from the outside, one tick at a time,
+//! while [`Game`] adds the [`PresentationPlugin`] and clicks through the screens.
+
+use std::{sync::Arc, thread, time::Duration};
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput, NativeKey, NativeKeyCode},
+        ButtonState, InputPlugin,
+    },
+    prelude::*,
+    render::render_resource::Shader,
+    state::app::StatesPlugin,
+    text::FontLoader,
+    time::TimeUpdateStrategy,
+};
+
+use crate::{
+    game::{
+        assets::{FontAssets, ImageAssets, SfxAssets, SoundtrackAssets},
+        credits::{Credits, CreditsHandle},
+        state::StateMachine,
+        storage::Storage,
+    },
+    ui::localization::{StringTable, StringTables},
+    PresentationPlugin, SimulationPlugin,
+};
+pub use crate::{
+    game::{
+        combat::{AttackKind, Health},
+        movement::KeyBindings,
+        net::{NetConnection, NetRole},
+        round::RoundOutcome,
+        score::MatchResults,
+        settings::Settings,
+        spawn::{
+            level::Level,
+            player::{Player, SpawnPlayer},
+        },
+        state::CharacterState,
+        waves::GameMode,
+    },
+    screen::Screen,
+    ui::{
+        interaction::Focused,
+        localization::Language,
+        scroll::ScrollList,
+        slider::Slider,
+        theme::Theme,
+        toggle::Toggle,
+        widgets::{Containers, Widgets},
+    },
+};
+
+/// How many times [`Game::finish_loading`] checks on fonts, string tables and credits
+/// before giving up on them.
+const LOAD_ATTEMPTS: usize = 500;
+
+/// The game's rules on top of the bare minimum of Bevy.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            InputPlugin,
+            TransformPlugin,
+            HierarchyPlugin,
+            AssetPlugin::default(),
+        ));
+
+        // Stand in for what rendering and the loading screen would provide.
+        app.init_asset::<Image>();
+        app.init_asset::<TextureAtlasLayout>();
+        app.init_asset::<Mesh>();
+        app.init_asset::<ColorMaterial>();
+        app.init_asset::<Shader>();
+        app.init_asset::<AudioSource>();
+        app.init_asset::<Font>();
+        app.init_asset_loader::<FontLoader>();
+        app.insert_resource(ImageAssets::placeholders());
+        app.insert_resource(FontAssets::placeholders());
+        // Saves, settings and high scores start out empty, and are gone when the app is.
+        app.insert_resource(Storage::in_memory());
+
+        app.add_plugins(SimulationPlugin);
+
+        // Every update advances time by exactly one tick, no matter how long it took.
+        let timestep = app.world().resource::<Time<Fixed>>().timestep();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+    }
+}
+
+/// A headless match between two human players that tests can play and inspect.
+pub struct Simulation {
+    app: App,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulation {
+    /// Starts a versus match with nobody spawned yet.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Playing);
+        // The first update only starts the clock, so it doesn't run a tick.
+        app.update();
+        Self { app }
+    }
+
+    /// Readies up to start a networked match over `connection`, as the host or the joining
+    /// player. The match starts once updates of both sides found each other, with nobody spawned.
+    pub fn online(mut connection: NetConnection) -> Self {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        let screen = match connection.role() {
+            NetRole::Host => Screen::Host,
+            NetRole::Join => Screen::Join,
+        };
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(screen);
+        connection.set_ready(true);
+        app.insert_resource(connection);
+        Self { app }
+    }
+
+    /// The app under test, for anything the methods below don't cover.
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn spawn_player(&mut self, player: Player, translation: Vec2) {
+        let world = self.app.world_mut();
+        world.trigger(SpawnPlayer {
+            player,
+            translation,
+        });
+        world.flush();
+    }
+
+    /// Presses a key. Like in the game, input is recorded after the frame's ticks ran,
+    /// so the key takes effect one tick late.
+    pub fn press(&mut self, key: KeyCode) {
+        self.send_key(key, ButtonState::Pressed);
+    }
+
+    /// Releases a key, which also takes effect one tick late.
+    pub fn release(&mut self, key: KeyCode) {
+        self.send_key(key, ButtonState::Released);
+    }
+
+    fn send_key(&mut self, key: KeyCode, state: ButtonState) {
+        send_key(&mut self.app, key, state);
+    }
+
+    /// Presses a key for a single frame.
+    pub fn tap(&mut self, key: KeyCode) {
+        self.press(key);
+        self.tick();
+        self.release(key);
+    }
+
+    pub fn tick(&mut self) {
+        self.app.update();
+    }
+
+    pub fn run_ticks(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// Where the player's feet are.
+    pub fn position(&mut self, player: Player) -> Vec2 {
+        self.get::<Transform>(player).translation.truncate()
+    }
+
+    pub fn health(&mut self, player: Player) -> f32 {
+        self.get::<Health>(player).current
+    }
+
+    pub fn state(&mut self, player: Player) -> CharacterState {
+        self.get::<StateMachine>(player).state()
+    }
+
+    pub fn screen(&self) -> Screen {
+        self.app.world().resource::<State<Screen>>().get().clone()
+    }
+
+    fn get<T: Component + Clone>(&mut self, player: Player) -> T {
+        let mut query = self.app.world_mut().query::<(&Player, &T)>();
+        query
+            .iter(self.app.world())
+            .find_map(|(&candidate, component)| (candidate == player).then(|| component.clone()))
+            .unwrap_or_else(|| panic!("{player:?} isn't spawned"))
+    }
+}
+
+/// The whole game from the splash screen on, with stand-ins for its assets.
+pub struct Game {
+    app: App,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugin, PresentationPlugin));
+        app.update();
+        Self { app }
+    }
+
+    /// A game that made it past the splash and loading screens to the title screen.
+    pub fn at_title() -> Self {
+        let mut game = Self::new();
+        game.run_for(Duration::from_secs(2));
+        game.finish_loading();
+        game.update();
+        game.update();
+        assert_eq!(game.screen(), Screen::Title);
+        game
+    }
+
+    /// The app under test, for anything the methods below don't cover.
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn screen(&self) -> Screen {
+        self.app.world().resource::<State<Screen>>().get().clone()
+    }
+
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// Runs updates until `duration` of game time passed.
+    pub fn run_for(&mut self, duration: Duration) {
+        let timestep = self.app.world().resource::<Time<Fixed>>().timestep();
+        let updates = duration.as_nanos().div_ceil(timestep.as_nanos());
+        for _ in 0..updates {
+            self.update();
+        }
+    }
+
+    /// Finishes loading everything the loading screen waits for. Fonts, string tables and
+    /// credits load for real, but there are no loaders for images and audio without rendering
+    /// and audio, so those get empty assets.
+    pub fn finish_loading(&mut self) {
+        // They load in the background, and only become available during an update.
+        for _ in 0..LOAD_ATTEMPTS {
+            let world = self.app.world();
+            if world
+                .resource::<FontAssets>()
+                .all_loaded(world.resource::<Assets<Font>>())
+                && world
+                    .resource::<StringTables>()
+                    .all_loaded(world.resource::<Assets<StringTable>>())
+                && world
+                    .resource::<CreditsHandle>()
+                    .all_loaded(world.resource::<Assets<Credits>>())
+            {
+                break;
+            }
+            self.update();
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let world = self.app.world_mut();
+        let images: Vec<_> = world.resource::<ImageAssets>().values().cloned().collect();
+        for image in images {
+            world
+                .resource_mut::<Assets<Image>>()
+                .insert(&image, Image::default());
+        }
+        let audio: Vec<_> = (world.resource::<SfxAssets>().values())
+            .chain(world.resource::<SoundtrackAssets>().values())
+            .cloned()
+            .collect();
+        for source in audio {
+            world.resource_mut::<Assets<AudioSource>>().insert(
+                &source,
+                AudioSource {
+                    bytes: Arc::new([]),
+                },
+            );
+        }
+    }
+
+    /// Holds a key down from the next frame on, until it is released.
+    pub fn press(&mut self, key: KeyCode) {
+        send_key(&mut self.app, key, ButtonState::Pressed);
+    }
+
+    pub fn release(&mut self, key: KeyCode) {
+        send_key(&mut self.app, key, ButtonState::Released);
+    }
+
+    /// Presses and releases a key, and runs the frames it takes the screen to react.
+    pub fn tap(&mut self, key: KeyCode) {
+        send_key(&mut self.app, key, ButtonState::Pressed);
+        self.update();
+        send_key(&mut self.app, key, ButtonState::Released);
+        self.update();
+    }
+
+    /// Types `text` one character per frame.
+    pub fn type_text(&mut self, text: &str) {
+        for character in text.chars() {
+            self.app.world_mut().send_event(KeyboardInput {
+                // Text fields go by the logical key.
+                key_code: KeyCode::Unidentified(NativeKeyCode::Unidentified),
+                logical_key: Key::Character(character.to_string().into()),
+                state: ButtonState::Pressed,
+                window: Entity::PLACEHOLDER,
+            });
+            self.update();
+        }
+    }
+
+    /// The labels of all buttons on screen.
+    pub fn buttons(&mut self) -> Vec<String> {
+        self.button_entities()
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect()
+    }
+
+    /// Clicks the button labelled `label`, and runs the frames it takes the screen to react.
+    pub fn click(&mut self, label: &str) {
+        let (button, _) = self
+            .button_entities()
+            .into_iter()
+            .find(|(_, candidate)| candidate == label)
+            .unwrap_or_else(|| panic!("There is no {label:?} button on {:?}", self.screen()));
+        *self.app.world_mut().get_mut::<Interaction>(button).unwrap() = Interaction::Pressed;
+        self.update();
+        if let Some(mut interaction) = self.app.world_mut().get_mut::<Interaction>(button) {
+            *interaction = Interaction::None;
+        }
+        self.update();
+    }
+
+    fn button_entities(&mut self) -> Vec<(Entity, String)> {
+        let world = self.app.world_mut();
+        let mut button_query = world.query_filtered::<(Entity, &Children), With<Button>>();
+        let mut text_query = world.query::<&Text>();
+        button_query
+            .iter(world)
+            .filter_map(|(button, children)| {
+                let text = text_query.iter_many(world, children).next()?;
+                let label = text.sections.iter().map(|section| &*section.value);
+                Some((button, label.collect()))
+            })
+            .collect()
+    }
+}
+
+fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+    let logical_key = match key_code {
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Escape => Key::Escape,
+        _ => Key::Unidentified(NativeKey::Unidentified),
+    };
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key,
+        state,
+        window: Entity::PLACEHOLDER,
+    });
+}
@@ -1,28 +1,23 @@
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod game;
+#[cfg(feature = "headless")]
+pub mod headless;
 mod screen;
 mod ui;
 
 use bevy::{
     asset::AssetMetaCheck,
     audio::{AudioPlugin, Volume},
+    ecs::schedule::ScheduleLabel,
     prelude::*,
 };
+use screen::Screen;
 
 pub struct AppPlugin;
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
-        // Order new `AppStep` variants by adding them here:
-        app.configure_sets(
-            Update,
-            (AppSet::TickTimers, AppSet::RecordInput, AppSet::Update).chain(),
-        );
-
-        // Spawn the main camera.
-        app.add_systems(Startup, spawn_camera);
-
         // Add Bevy plugins.
         app.add_plugins(
             DefaultPlugins
@@ -53,7 +48,7 @@ impl Plugin for AppPlugin {
         );
 
         // Add other plugins.
-        app.add_plugins((game::plugin, screen::plugin, ui::plugin));
+        app.add_plugins((SimulationPlugin, PresentationPlugin));
 
         // Enable dev tools for dev builds.
         #[cfg(feature = "dev")]
@@ -61,7 +56,51 @@ impl Plugin for AppPlugin {
     }
 }
 
-/// High-level groupings of systems for the app in the `Update` schedule.
+/// The game's rules, without a window, audio or rendering.
+/// This runs on top of [`MinimalPlugins`], see [`headless`].
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        // Order new `AppStep` variants by adding them here:
+        for schedule in [Update.intern(), FixedUpdate.intern()] {
+            app.configure_sets(
+                schedule,
+                (
+                    AppSet::TickTimers,
+                    AppSet::RecordInput,
+                    AppSet::BufferInput,
+                    AppSet::ChangeState,
+                    AppSet::Update,
+                )
+                    .chain(),
+            );
+        }
+
+        // The game simulates in fixed ticks, so that it plays out the same way every time.
+        app.insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ));
+
+        app.init_state::<Screen>();
+        app.enable_state_scoped_entities::<Screen>();
+
+        app.add_plugins(game::simulation_plugin);
+    }
+}
+
+/// The screens, visuals and audio on top of the [`SimulationPlugin`].
+pub struct PresentationPlugin;
+
+impl Plugin for PresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((game::presentation_plugin, screen::plugin, ui::plugin));
+    }
+}
+
+/// How many simulation ticks run per second.
+const SIMULATION_HZ: f64 = 60.0;
+
+/// High-level groupings of systems for the app in the `Update` and `FixedUpdate` schedules.
+/// Gameplay runs in `FixedUpdate`, while input and presentation run in `Update`.
 /// When adding a new variant, make sure to order it in the `configure_sets`
 /// call above.
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -70,14 +109,10 @@ enum AppSet {
     TickTimers,
     /// Record player input.
     RecordInput,
+    /// Remember recent input for combos.
+    BufferInput,
+    /// Move characters between states based on their input.
+    ChangeState,
     /// Do everything else (consider splitting this into further variants).
     Update,
 }
-
-fn spawn_camera(mut commands: Commands) {
-    commands.spawn((
-        Name::new("Camera"),
-        Camera2dBundle::default(),
-        IsDefaultUiCamera,
-    ));
-}
@@ -1,9 +1,17 @@
 //! A credits screen that can be accessed from the title screen.
+//! The credits roll up the screen, faster while Space or the gamepad's right shoulder button is held,
+//! and start over once they're through. Back skips them.
 
 use bevy::prelude::*;
 
 use super::Screen;
-use crate::{game::audio::soundtrack::Soundtrack, ui::prelude::*};
+use crate::{
+    game::{
+        audio::soundtrack::Soundtrack,
+        credits::{Credits, CreditsHandle},
+    },
+    ui::{prelude::*, theme::ThemedNode},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Credits), enter_credits);
@@ -11,32 +19,90 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        handle_credits_action.run_if(in_state(Screen::Credits)),
+        (handle_credits_action, roll_credits).run_if(in_state(Screen::Credits)),
     );
-    app.register_type::<CreditsAction>();
+    app.register_type::<(CreditsAction, CreditsRoll)>();
 }
 
+/// How fast the credits roll, in pixels per second.
+const ROLL_SPEED: f32 = 60.0;
+
+/// How many times faster the credits roll while sped up.
+const FAST_FORWARD: f32 = 5.0;
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Component)]
 enum CreditsAction {
     Back,
 }
 
-fn enter_credits(mut commands: Commands) {
+/// The area the credits roll through. Its child holds the credits,
+/// which move up by `offset` from just below the area's bottom edge.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+struct CreditsRoll {
+    offset: f32,
+}
+
+fn enter_credits(
+    mut commands: Commands,
+    credits_handle: Res<CreditsHandle>,
+    credits: Res<Assets<Credits>>,
+) {
     commands
         .ui_root()
         .insert(StateScoped(Screen::Credits))
         .with_children(|children| {
-            children.header("Made by");
-            children.label("Alice - Foo");
-            children.label("Bob - Bar");
+            children
+                .spawn((
+                    Name::new("Credits Roll"),
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            flex_grow: 1.0,
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            overflow: Overflow::clip(),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    CreditsRoll::default(),
+                ))
+                .with_children(|children| {
+                    children
+                        .spawn((
+                            Name::new("Credits"),
+                            NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            ThemedNode::ScrollList,
+                        ))
+                        .with_children(|children| {
+                            let Some(credits) = credits.get(&**credits_handle) else {
+                                return;
+                            };
+                            for section in &credits.sections {
+                                children.header(Localized::new(&section.heading));
+                                for entry in &section.entries {
+                                    children.label(entry.title());
+                                    if let Some(license) = &entry.license {
+                                        children.label(license.as_str());
+                                    }
+                                }
+                            }
+                        });
+                });
 
-            children.header("Assets");
-            children.label("Bevy logo - CC0 by the Bevy Foundation");
-            children.label("Ducky sprite - CC0 by Caz Creates Games");
-            children.label("Music - CC BY 3.0 by Kevin MacLeod");
-
-            children.button("Back").insert(CreditsAction::Back);
+            children.label(Localized::new("credits-hint"));
+            children
+                .button(Localized::new("back"))
+                .insert((CreditsAction::Back, BackButton));
         });
 
     commands.trigger(Soundtrack::Credits);
@@ -58,3 +124,36 @@ fn handle_credits_action(
         }
     }
 }
+
+fn roll_credits(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    mut roll_query: Query<(&mut CreditsRoll, &Node, &Children)>,
+    mut credits_query: Query<(&Node, &mut Style), Without<CreditsRoll>>,
+) {
+    let sped_up = keyboard_input.pressed(KeyCode::Space)
+        || gamepads.iter().any(|gamepad| {
+            gamepad_input.pressed(GamepadButton::new(gamepad, GamepadButtonType::RightTrigger))
+        });
+    let speed = if sped_up {
+        ROLL_SPEED * FAST_FORWARD
+    } else {
+        ROLL_SPEED
+    };
+
+    for (mut roll, node, children) in &mut roll_query {
+        roll.offset += speed * time.delta_seconds();
+        let height = node.size().y;
+        let mut credits = credits_query.iter_many_mut(children);
+        while let Some((credits_node, mut style)) = credits.fetch_next() {
+            // Start over once the last line has left the top. Before layout, there's no telling.
+            let credits_height = credits_node.size().y;
+            if credits_height > 0.0 && roll.offset > height + credits_height {
+                roll.offset = 0.0;
+            }
+            style.top = Val::Px(height - roll.offset);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! A high score screen that can be accessed from the title screen.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::{game::high_scores::HighScores, ui::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::HighScores), enter_high_scores);
+
+    app.add_systems(
+        Update,
+        handle_high_scores_action.run_if(in_state(Screen::HighScores)),
+    );
+    app.register_type::<HighScoresAction>();
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum HighScoresAction {
+    Back,
+}
+
+fn enter_high_scores(mut commands: Commands, high_scores: Res<HighScores>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::HighScores))
+        .with_children(|children| {
+            children.header(Localized::new("high-scores"));
+            if high_scores.entries.is_empty() {
+                children.label(Localized::new("no-scores-yet"));
+            }
+            children
+                .scroll_list(Val::Px(400.0))
+                .with_children(|children| {
+                    for (rank, entry) in high_scores.entries.iter().enumerate() {
+                        children.label(format!(
+                            "{}. {}  {}  {}  {}",
+                            rank + 1,
+                            entry.name,
+                            entry.character,
+                            entry.score,
+                            entry.date
+                        ));
+                    }
+                });
+
+            children
+                .button(Localized::new("back"))
+                .insert((HighScoresAction::Back, BackButton));
+        });
+}
+
+fn handle_high_scores_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&HighScoresAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                HighScoresAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
@@ -6,10 +6,14 @@ use bevy::prelude::*;
 use super::Screen;
 use crate::{
     game::{
-        assets::{ImageAssets, SfxAssets, SoundtrackAssets, UiAssets},
+        assets::{FontAssets, ImageAssets, SfxAssets, SoundtrackAssets, UiAssets},
+        credits::{Credits, CreditsHandle},
         spawn::ui::HealthBarUiMaterial,
     },
-    ui::prelude::*,
+    ui::{
+        localization::{StringTable, StringTables},
+        prelude::*,
+    },
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -26,30 +30,41 @@ fn enter_loading(
         .ui_root()
         .insert(StateScoped(Screen::Loading))
         .with_children(|children| {
-            children.label("Loading...");
+            children.label(Localized::new("loading"));
         });
 
     // Preload assets so the game runs smoothly.
     commands.insert_resource(ImageAssets::new(&asset_server));
+    commands.insert_resource(FontAssets::new(&asset_server));
     commands.insert_resource(SfxAssets::new(&asset_server));
     commands.insert_resource(SoundtrackAssets::new(&asset_server));
     commands.insert_resource(UiAssets::new(&asset_server, &mut health_bar_ui_materials));
+    commands.insert_resource(CreditsHandle::new(&asset_server));
 }
 
 fn check_all_loaded(
     image_assets: Res<Assets<Image>>,
     audio_assets: Res<Assets<AudioSource>>,
+    font_assets: Res<Assets<Font>>,
+    string_table_assets: Res<Assets<StringTable>>,
+    credits_assets: Res<Assets<Credits>>,
     health_bar_ui_materials: Res<Assets<HealthBarUiMaterial>>,
     images: Res<ImageAssets>,
+    fonts: Res<FontAssets>,
     sfxs: Res<SfxAssets>,
     soundtracks: Res<SoundtrackAssets>,
     ui_assets: Res<UiAssets>,
+    string_tables: Res<StringTables>,
+    credits: Res<CreditsHandle>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
     let all_loaded = images.all_loaded(&image_assets)
+        && fonts.all_loaded(&font_assets)
         && sfxs.all_loaded(&audio_assets)
         && soundtracks.all_loaded(&audio_assets)
-        && ui_assets.all_loaded(&health_bar_ui_materials);
+        && ui_assets.all_loaded(&health_bar_ui_materials)
+        && string_tables.all_loaded(&string_table_assets)
+        && credits.all_loaded(&credits_assets);
     if all_loaded {
         next_screen.set(Screen::Title);
     }
@@ -0,0 +1,54 @@
+//! The lobby for networked matches, where players choose to host or join one.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::ui::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Lobby), enter_lobby);
+
+    app.register_type::<LobbyAction>();
+    app.add_systems(Update, handle_lobby_action.run_if(in_state(Screen::Lobby)));
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum LobbyAction {
+    Host,
+    Join,
+    Back,
+}
+
+fn enter_lobby(mut commands: Commands) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Lobby))
+        .with_children(|children| {
+            children.header(Localized::new("online"));
+            children
+                .button(Localized::new("host"))
+                .insert(LobbyAction::Host);
+            children
+                .button(Localized::new("join"))
+                .insert(LobbyAction::Join);
+            children
+                .button(Localized::new("back"))
+                .insert((LobbyAction::Back, BackButton));
+        });
+}
+
+fn handle_lobby_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&LobbyAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            next_screen.set(match action {
+                LobbyAction::Host => Screen::Host,
+                LobbyAction::Join => Screen::Join,
+                LobbyAction::Back => Screen::Title,
+            });
+        }
+    }
+}
@@ -1,23 +1,32 @@
 //! The game's main screen states and transitions between them.
 
 mod credits;
+mod high_scores;
 mod loading;
+mod lobby;
+mod new_high_score;
+mod online;
 mod playing;
+mod results;
+mod settings;
 mod splash;
 mod title;
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_state::<Screen>();
-    app.enable_state_scoped_entities::<Screen>();
-
     app.add_plugins((
         splash::plugin,
         loading::plugin,
         title::plugin,
         credits::plugin,
         playing::plugin,
+        results::plugin,
+        new_high_score::plugin,
+        high_scores::plugin,
+        lobby::plugin,
+        online::plugin,
+        settings::plugin,
     ));
 }
 
@@ -30,4 +39,11 @@ pub enum Screen {
     Title,
     Credits,
     Playing,
+    Results,
+    NewHighScore,
+    HighScores,
+    Settings,
+    Lobby,
+    Host,
+    Join,
 }
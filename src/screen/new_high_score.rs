@@ -0,0 +1,183 @@
+//! The screen where players enter their initials after setting a high score.
+//! Players whose score made it onto the table take turns typing their initials,
+//! then the updated table is shown.
+
+use std::{cmp::Reverse, collections::VecDeque};
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use super::Screen;
+use crate::{
+    game::{
+        ai::Opponent,
+        assets::FontAsset,
+        high_scores::{HighScoreEntry, HighScores},
+        score::{MatchResults, PlayerResults},
+    },
+    ui::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::NewHighScore), enter_new_high_score);
+    app.add_systems(OnExit(Screen::NewHighScore), exit_new_high_score);
+
+    app.add_systems(
+        Update,
+        (type_initials, update_initials_ui)
+            .chain()
+            .run_if(in_state(Screen::NewHighScore)),
+    );
+}
+
+/// How many letters players get for their name.
+const INITIALS_LENGTH: usize = 3;
+
+/// The players still entering their initials, and what the current one typed so far.
+#[derive(Resource, Debug, Default)]
+struct InitialsEntry {
+    pending: VecDeque<PlayerResults>,
+    initials: String,
+}
+
+#[derive(Component)]
+struct PlayerText;
+
+#[derive(Component)]
+struct InitialsText;
+
+/// The human players of the last match whose scores would make it onto the table.
+pub(super) fn qualifying_players(
+    results: &MatchResults,
+    high_scores: &HighScores,
+    opponent: Opponent,
+) -> VecDeque<PlayerResults> {
+    let mut players: VecDeque<_> = results
+        .players
+        .iter()
+        .filter(|player| !opponent.controls(player.player) && high_scores.qualifies(player.score))
+        .copied()
+        .collect();
+    players
+        .make_contiguous()
+        .sort_by_key(|player| Reverse(player.score));
+    players
+}
+
+fn enter_new_high_score(
+    mut commands: Commands,
+    results: Res<MatchResults>,
+    high_scores: Res<HighScores>,
+    opponent: Res<Opponent>,
+) {
+    commands.insert_resource(InitialsEntry {
+        pending: qualifying_players(&results, &high_scores, *opponent),
+        initials: String::new(),
+    });
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::NewHighScore))
+        .with_children(|children| {
+            children.header(Localized::new("new-high-score"));
+            children
+                .label(Localized::new("player-score"))
+                .insert(PlayerText);
+            children.header("").insert(InitialsText);
+            children.label(Localized::new("type-initials"));
+        });
+}
+
+fn exit_new_high_score(mut commands: Commands) {
+    commands.remove_resource::<InitialsEntry>();
+}
+
+fn type_initials(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut entry: ResMut<InitialsEntry>,
+    mut high_scores: ResMut<HighScores>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    for input in keyboard_input.read() {
+        if input.state != ButtonState::Pressed {
+            continue;
+        }
+        match &input.logical_key {
+            Key::Character(characters) => {
+                // Any script works, as long as the font can show it.
+                let letters = characters
+                    .chars()
+                    .filter(|letter| letter.is_alphanumeric())
+                    .flat_map(char::to_uppercase)
+                    .filter(|&letter| FontAsset::Pixel.has_glyph(letter));
+                for letter in letters {
+                    if entry.initials.chars().count() < INITIALS_LENGTH {
+                        entry.initials.push(letter);
+                    }
+                }
+            }
+            Key::Backspace => {
+                entry.initials.pop();
+            }
+            Key::Enter if !entry.initials.is_empty() => {
+                let initials = std::mem::take(&mut entry.initials);
+                if let Some(player) = entry.pending.pop_front() {
+                    high_scores.insert(HighScoreEntry::new(initials, player.player, player.score));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Earlier entries may have pushed later ones off the table.
+    while let Some(player) = entry.pending.front() {
+        if high_scores.qualifies(player.score) {
+            break;
+        }
+        entry.pending.pop_front();
+    }
+    if entry.pending.is_empty() {
+        next_screen.set(Screen::HighScores);
+    }
+}
+
+fn update_initials_ui(
+    entry: Res<InitialsEntry>,
+    player_text_query: Query<&Children, With<PlayerText>>,
+    initials_text_query: Query<&Children, With<InitialsText>>,
+    mut localized_query: Query<&mut LocalizedText>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !entry.is_changed() {
+        return;
+    }
+    let Some(player) = entry.pending.front() else {
+        return;
+    };
+    let mut initials = entry.initials.clone();
+    for _ in initials.chars().count()..INITIALS_LENGTH {
+        initials.push('_');
+    }
+    for children in &player_text_query {
+        let mut texts = localized_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.text = Localized::new("player-score")
+                .with_arg("player", player.player.name())
+                .with_arg("score", player.score.to_string());
+        }
+    }
+    for children in &initials_text_query {
+        set_text(&mut text_query, children, initials.clone());
+    }
+}
+
+fn set_text(text_query: &mut Query<&mut Text>, children: &Children, value: String) {
+    let mut texts = text_query.iter_many_mut(children);
+    while let Some(mut text) = texts.fetch_next() {
+        text.sections[0].value.clone_from(&value);
+    }
+}
@@ -0,0 +1,338 @@
+//! The screens for hosting and joining networked matches.
+//! Players enter a port or the host's address, wait for each other and ready up.
+//! The host's address is looked up in the background, so the screen keeps running meanwhile.
+//! The match starts once both are ready, and losing the connection brings players back here.
+
+use std::net::{SocketAddr, ToSocketAddrs};
+
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, IoTaskPool, Task},
+};
+
+use super::Screen;
+use crate::{
+    game::net::{Disconnect, Disconnected, NetConnection, NetRole},
+    ui::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LobbyMessage>();
+    app.add_systems(OnEnter(Screen::Host), enter_online);
+    app.add_systems(OnEnter(Screen::Join), enter_online);
+    app.add_systems(OnExit(Screen::Host), (clear_lobby_message, cancel_lookup));
+    app.add_systems(OnExit(Screen::Join), (clear_lobby_message, cancel_lookup));
+
+    app.register_type::<OnlineAction>();
+    app.add_systems(
+        Update,
+        (
+            lock_address,
+            handle_online_action,
+            finish_lookup,
+            update_online_ui,
+        )
+            .chain()
+            .run_if(in_state(Screen::Host).or_else(in_state(Screen::Join))),
+    );
+    // Connections made from the command line skip the lobby.
+    app.add_systems(
+        Update,
+        open_online_screen
+            .run_if(in_state(Screen::Title).and_then(resource_exists::<NetConnection>)),
+    );
+    app.observe(return_to_online_screen);
+}
+
+/// How long addresses can get.
+const MAX_ADDRESS_LENGTH: usize = 64;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum OnlineAction {
+    /// Host or join, depending on the screen.
+    Connect,
+    /// Toggle being ready.
+    Ready,
+    Back,
+}
+
+/// The port to host on, or the address to join.
+#[derive(Component, Debug)]
+struct AddressInput;
+
+/// Why the last connection ended or couldn't be made.
+#[derive(Resource, Debug, Default)]
+struct LobbyMessage(Option<Localized>);
+
+/// The host's address being looked up before joining.
+#[derive(Resource)]
+struct Lookup {
+    address: String,
+    task: Task<Option<SocketAddr>>,
+}
+
+/// The texts that change while on the screen.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum OnlineText {
+    Status,
+    Latency,
+    Ready,
+}
+
+fn role(screen: &Screen) -> NetRole {
+    if *screen == Screen::Host {
+        NetRole::Host
+    } else {
+        NetRole::Join
+    }
+}
+
+fn online_screen(role: NetRole) -> Screen {
+    match role {
+        NetRole::Host => Screen::Host,
+        NetRole::Join => Screen::Join,
+    }
+}
+
+fn enter_online(mut commands: Commands, screen: Res<State<Screen>>) {
+    let role = role(screen.get());
+    let (title, prompt, address, allowed, connect) = match role {
+        NetRole::Host => ("host-a-match", "port", "7000", CharacterSet::Digits, "host"),
+        NetRole::Join => (
+            "join-a-match",
+            "host-address",
+            "127.0.0.1:7000",
+            CharacterSet::Alphanumeric(".:-[]"),
+            "join",
+        ),
+    };
+    commands
+        .ui_root()
+        .insert(StateScoped(online_screen(role)))
+        .with_children(|children| {
+            children.header(Localized::new(title));
+            children.label(Localized::new(prompt));
+            children
+                .text_input(TextInput::new(address, MAX_ADDRESS_LENGTH, allowed))
+                .insert((AddressInput, Focused));
+            children
+                .button(Localized::new(connect))
+                .insert(OnlineAction::Connect);
+            children.label("").insert(OnlineText::Status);
+            children.label("").insert(OnlineText::Latency);
+            children.label("").insert(OnlineText::Ready);
+            children
+                .button(Localized::new("ready"))
+                .insert(OnlineAction::Ready);
+            children
+                .button(Localized::new("back"))
+                .insert((OnlineAction::Back, BackButton));
+        });
+}
+
+fn clear_lobby_message(mut message: ResMut<LobbyMessage>) {
+    message.0 = None;
+}
+
+/// Dropping the task cancels it.
+fn cancel_lookup(mut commands: Commands) {
+    commands.remove_resource::<Lookup>();
+}
+
+/// The address can't change once connected.
+fn lock_address(
+    mut commands: Commands,
+    connection: Option<Res<NetConnection>>,
+    address_query: Query<Entity, (With<AddressInput>, With<Focused>)>,
+) {
+    if connection.is_none() {
+        return;
+    }
+    for entity in &address_query {
+        commands.entity(entity).remove::<Focused>();
+    }
+}
+
+fn handle_online_action(
+    mut commands: Commands,
+    screen: Res<State<Screen>>,
+    address_query: Query<&TextInput, With<AddressInput>>,
+    mut connection: Option<ResMut<NetConnection>>,
+    lookup: Option<Res<Lookup>>,
+    mut message: ResMut<LobbyMessage>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&OnlineAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        match action {
+            OnlineAction::Connect if connection.is_none() && lookup.is_none() => {
+                let Ok(address) = address_query.get_single() else {
+                    continue;
+                };
+                let address = address.value.clone();
+                match role(screen.get()) {
+                    NetRole::Host => {
+                        let result = address
+                            .parse()
+                            .map_err(|_| Localized::new("not-a-port").with_arg("address", address))
+                            .and_then(|port| {
+                                NetConnection::host(port).map_err(|error| {
+                                    Localized::new("cannot-host")
+                                        .with_arg("port", port.to_string())
+                                        .with_arg("error", error.to_string())
+                                })
+                            });
+                        connect(&mut commands, &mut message, result);
+                    }
+                    NetRole::Join => {
+                        // Host names may have to be asked for over the network.
+                        let lookup = address.clone();
+                        let task = IoTaskPool::get().spawn(async move {
+                            lookup
+                                .to_socket_addrs()
+                                .ok()
+                                .and_then(|mut addresses| addresses.next())
+                        });
+                        commands.insert_resource(Lookup { address, task });
+                        message.0 = None;
+                    }
+                }
+            }
+            OnlineAction::Connect => {}
+            OnlineAction::Ready => {
+                if let Some(connection) = connection.as_mut().filter(|c| c.connected()) {
+                    let ready = !connection.ready();
+                    connection.set_ready(ready);
+                }
+            }
+            OnlineAction::Back => {
+                commands.add(Disconnect {
+                    reason: Localized::new("left-lobby"),
+                });
+                next_screen.set(Screen::Lobby);
+            }
+        }
+    }
+}
+
+/// Use the new connection, or show why there is none.
+fn connect(
+    commands: &mut Commands,
+    message: &mut LobbyMessage,
+    result: Result<NetConnection, Localized>,
+) {
+    match result {
+        Ok(connection) => {
+            commands.insert_resource(connection);
+            message.0 = None;
+        }
+        Err(error) => message.0 = Some(error),
+    }
+}
+
+/// Join the host once its address is found.
+fn finish_lookup(
+    mut commands: Commands,
+    lookup: Option<ResMut<Lookup>>,
+    mut message: ResMut<LobbyMessage>,
+) {
+    let Some(mut lookup) = lookup else {
+        return;
+    };
+    let Some(found) = block_on(future::poll_once(&mut lookup.task)) else {
+        return;
+    };
+    commands.remove_resource::<Lookup>();
+    let result = found
+        .ok_or_else(|| Localized::new("cannot-find").with_arg("address", &*lookup.address))
+        .and_then(|address| {
+            NetConnection::join(address).map_err(|error| {
+                Localized::new("cannot-join")
+                    .with_arg("address", address.to_string())
+                    .with_arg("error", error.to_string())
+            })
+        });
+    connect(&mut commands, &mut message, result);
+}
+
+fn update_online_ui(
+    connection: Option<Res<NetConnection>>,
+    lookup: Option<Res<Lookup>>,
+    message: Res<LobbyMessage>,
+    localizer: Localizer,
+    online_text_query: Query<(&OnlineText, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let status = match (&connection, &lookup, &message.0) {
+        (Some(connection), ..) => localizer.get(&connection_status(connection)),
+        (None, Some(lookup), _) => {
+            localizer.get(&Localized::new("looking-up").with_arg("address", &*lookup.address))
+        }
+        (None, None, Some(message)) => localizer.get(message),
+        (None, None, None) => localizer.get(&Localized::new("not-connected")),
+    };
+    let latency = match connection.as_ref().and_then(|c| c.latency()) {
+        Some(latency) => Localized::new("ping").with_arg("ms", latency.as_millis().to_string()),
+        None => Localized::new("ping-unknown"),
+    };
+    let latency = localizer.get(&latency);
+    let ready = match &connection {
+        Some(connection) if connection.connected() => localizer.get(
+            &Localized::new("ready-status")
+                .with_arg("you", ready_text(connection.ready()))
+                .with_arg("them", ready_text(connection.peer_ready())),
+        ),
+        _ => String::new(),
+    };
+
+    for (online_text, children) in &online_text_query {
+        let value = match online_text {
+            OnlineText::Status => &status,
+            OnlineText::Latency => &latency,
+            OnlineText::Ready => &ready,
+        };
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            if text.sections[0].value != *value {
+                text.sections[0].value.clone_from(value);
+            }
+        }
+    }
+}
+
+fn connection_status(connection: &NetConnection) -> Localized {
+    match (connection.connected(), connection.peer()) {
+        (true, Some(peer)) => Localized::new("connected-to").with_arg("peer", peer.to_string()),
+        (_, Some(peer)) => Localized::new("connecting-to").with_arg("peer", peer.to_string()),
+        (_, None) => match connection.local_port() {
+            Some(port) => Localized::new("waiting-on-port").with_arg("port", port.to_string()),
+            None => Localized::new("waiting-for-player"),
+        },
+    }
+}
+
+fn ready_text(ready: bool) -> Localized {
+    Localized::new(if ready { "ready" } else { "not-ready" })
+}
+
+fn open_online_screen(connection: Res<NetConnection>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(online_screen(connection.role()));
+}
+
+/// Come back here when the connection ends during or after a match.
+fn return_to_online_screen(
+    trigger: Trigger<Disconnected>,
+    screen: Res<State<Screen>>,
+    mut message: ResMut<LobbyMessage>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let event = trigger.event();
+    message.0 = Some(event.reason.clone());
+    if matches!(screen.get(), Screen::Playing | Screen::Results) {
+        next_screen.set(online_screen(event.role));
+    }
+}
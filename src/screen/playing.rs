@@ -3,9 +3,15 @@
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
 use super::Screen;
-use crate::game::{
-    audio::soundtrack::Soundtrack,
-    spawn::{level::SpawnLevel, ui::SpawnGameUi},
+use crate::{
+    game::{
+        audio::soundtrack::Soundtrack,
+        net::{Disconnect, NetSession},
+        replay::ReplayPlayback,
+        save::SaveMatch,
+        spawn::{level::SpawnLevel, ui::SpawnGameUi},
+    },
+    ui::localization::Localized,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -30,6 +36,22 @@ fn exit_playing(mut commands: Commands) {
     commands.trigger(Soundtrack::Disable);
 }
 
-fn return_to_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
+fn return_to_title_screen(
+    mut commands: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    session: Option<Res<NetSession>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    // The other player is told, and both go back to the lobby.
+    if session.is_some() {
+        commands.add(Disconnect {
+            reason: Localized::new("left-match"),
+        });
+        return;
+    }
+    // Pick up where we left off next time.
+    if playback.is_none() {
+        commands.add(SaveMatch);
+    }
     next_screen.set(Screen::Title);
 }
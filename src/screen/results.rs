@@ -0,0 +1,93 @@
+//! The results screen that appears after a match.
+
+use bevy::prelude::*;
+
+use super::{new_high_score::qualifying_players, Screen};
+use crate::{
+    game::{
+        ai::Opponent,
+        high_scores::HighScores,
+        net::{NetConnection, NetRole},
+        replay::ReplayPlayback,
+        score::MatchResults,
+    },
+    ui::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Results), enter_results);
+
+    app.add_systems(
+        Update,
+        handle_results_action.run_if(in_state(Screen::Results)),
+    );
+    app.register_type::<ResultsAction>();
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum ResultsAction {
+    Continue,
+}
+
+fn enter_results(mut commands: Commands, results: Res<MatchResults>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Results))
+        .with_children(|children| {
+            if let Some(outcome) = results.outcome {
+                children.header(outcome.text());
+            }
+            for player in &results.players {
+                children.header(player.player.name());
+                children.label(
+                    Localized::new("results-score").with_arg("score", player.score.to_string()),
+                );
+                children.label(
+                    Localized::new("results-stats")
+                        .with_arg("hits", player.hits.to_string())
+                        .with_arg("knockouts", player.knockouts.to_string())
+                        .with_arg("combo", player.best_combo.to_string()),
+                );
+            }
+
+            children
+                .button(Localized::new("continue"))
+                .insert(ResultsAction::Continue);
+        });
+}
+
+fn handle_results_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    results: Res<MatchResults>,
+    high_scores: Res<HighScores>,
+    opponent: Res<Opponent>,
+    playback: Option<Res<ReplayPlayback>>,
+    connection: Option<Res<NetConnection>>,
+    mut button_query: InteractionQuery<&ResultsAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                ResultsAction::Continue => {
+                    // Networked players go back to the lobby for another match.
+                    if let Some(connection) = &connection {
+                        next_screen.set(match connection.role() {
+                            NetRole::Host => Screen::Host,
+                            NetRole::Join => Screen::Join,
+                        });
+                        continue;
+                    }
+                    // Watching a replay doesn't set new scores.
+                    let new_high_score = playback.is_none()
+                        && !qualifying_players(&results, &high_scores, *opponent).is_empty();
+                    next_screen.set(if new_high_score {
+                        Screen::NewHighScore
+                    } else {
+                        Screen::Title
+                    });
+                }
+            }
+        }
+    }
+}
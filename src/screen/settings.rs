@@ -0,0 +1,75 @@
+//! A settings screen that can be accessed from the title screen.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::{game::settings::Settings, ui::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Settings), enter_settings);
+
+    app.register_type::<SettingsAction>();
+    app.add_systems(
+        Update,
+        (handle_settings_action, select_language).run_if(in_state(Screen::Settings)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum SettingsAction {
+    Back,
+}
+
+/// Picks the language of menus and the HUD.
+#[derive(Component, Debug)]
+struct LanguageSelector;
+
+fn enter_settings(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Settings))
+        .with_children(|children| {
+            children.header(Localized::new("settings"));
+            let selected = Language::ALL
+                .iter()
+                .position(|language| language == &settings.language)
+                .unwrap_or_default();
+            children
+                .cycle(
+                    Localized::new("language"),
+                    Cycle::new(Language::ALL.map(Language::name), selected),
+                )
+                .insert(LanguageSelector);
+
+            children
+                .button(Localized::new("back"))
+                .insert((SettingsAction::Back, BackButton));
+        });
+}
+
+fn handle_settings_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&SettingsAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                SettingsAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
+
+fn select_language(
+    mut settings: ResMut<Settings>,
+    selector_query: Query<&Cycle, (With<LanguageSelector>, Changed<Cycle>)>,
+) {
+    for cycle in &selector_query {
+        if let Some(&language) = Language::ALL.get(cycle.selected) {
+            if settings.language != language {
+                settings.language = language;
+            }
+        }
+    }
+}
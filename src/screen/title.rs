@@ -3,47 +3,140 @@
 use bevy::prelude::*;
 
 use super::Screen;
-use crate::ui::prelude::*;
+use crate::{
+    game::{
+        ai::{Difficulty, Opponent},
+        replay::{has_replay, WatchReplay},
+        save::{has_saved_match, ResumeMatch},
+        storage::Storage,
+        waves::GameMode,
+    },
+    ui::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Title), enter_title);
 
     app.register_type::<TitleAction>();
-    app.add_systems(Update, handle_title_action.run_if(in_state(Screen::Title)));
+    app.add_systems(
+        Update,
+        (handle_title_action, select_difficulty).run_if(in_state(Screen::Title)),
+    );
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Component)]
 enum TitleAction {
+    /// Resume the match that was left early.
+    Continue,
     Play,
+    PlayCpu,
+    /// Fight waves of grunts together.
+    CoOp,
+    /// Play against someone else over the network.
+    #[cfg(not(target_family = "wasm"))]
+    Online,
+    HighScores,
+    Settings,
+    /// Watch the last match again.
+    Replay,
     Credits,
     /// Exit doesn't work well with embedded applications.
     #[cfg(not(target_family = "wasm"))]
     Exit,
 }
 
-fn enter_title(mut commands: Commands) {
+/// Picks the AI's difficulty level.
+#[derive(Component, Debug)]
+struct DifficultySelector;
+
+fn enter_title(mut commands: Commands, difficulty: Res<Difficulty>, storage: Res<Storage>) {
     commands
         .ui_root()
         .insert(StateScoped(Screen::Title))
         .with_children(|children| {
-            children.button("Play").insert(TitleAction::Play);
-            children.button("Credits").insert(TitleAction::Credits);
+            if has_saved_match(&storage) {
+                children
+                    .button(Localized::new("continue"))
+                    .insert(TitleAction::Continue);
+            }
+            children
+                .button(Localized::new("play"))
+                .insert(TitleAction::Play);
+            children
+                .button(Localized::new("play-vs-cpu"))
+                .insert(TitleAction::PlayCpu);
+            let selected = Difficulty::ALL
+                .iter()
+                .position(|level| level == &*difficulty)
+                .unwrap_or_default();
+            children
+                .cycle(
+                    Localized::new("cpu"),
+                    Cycle::new(Difficulty::ALL.map(|level| level.name()), selected),
+                )
+                .insert(DifficultySelector);
+            children
+                .button(Localized::new("co-op"))
+                .insert(TitleAction::CoOp);
+            #[cfg(not(target_family = "wasm"))]
+            children
+                .button(Localized::new("online"))
+                .insert(TitleAction::Online);
+            children
+                .button(Localized::new("high-scores"))
+                .insert(TitleAction::HighScores);
+            if has_replay(&storage) {
+                children
+                    .button(Localized::new("replay"))
+                    .insert(TitleAction::Replay);
+            }
+            children
+                .button(Localized::new("settings"))
+                .insert(TitleAction::Settings);
+            children
+                .button(Localized::new("credits"))
+                .insert(TitleAction::Credits);
 
             #[cfg(not(target_family = "wasm"))]
-            children.button("Exit").insert(TitleAction::Exit);
+            children
+                .button(Localized::new("exit"))
+                .insert(TitleAction::Exit);
         });
 }
 
 fn handle_title_action(
+    mut commands: Commands,
     mut next_screen: ResMut<NextState<Screen>>,
+    mut mode: ResMut<GameMode>,
+    mut opponent: ResMut<Opponent>,
     mut button_query: InteractionQuery<&TitleAction>,
     #[cfg(not(target_family = "wasm"))] mut app_exit: EventWriter<AppExit>,
 ) {
     for (interaction, action) in &mut button_query {
         if matches!(interaction, Interaction::Pressed) {
             match action {
-                TitleAction::Play => next_screen.set(Screen::Playing),
+                TitleAction::Continue => commands.add(ResumeMatch),
+                TitleAction::Play => {
+                    *mode = GameMode::Versus;
+                    *opponent = Opponent::Human;
+                    next_screen.set(Screen::Playing);
+                }
+                TitleAction::PlayCpu => {
+                    *mode = GameMode::Versus;
+                    *opponent = Opponent::Cpu;
+                    next_screen.set(Screen::Playing);
+                }
+                TitleAction::CoOp => {
+                    *mode = GameMode::Waves;
+                    *opponent = Opponent::Human;
+                    next_screen.set(Screen::Playing);
+                }
+                #[cfg(not(target_family = "wasm"))]
+                TitleAction::Online => next_screen.set(Screen::Lobby),
+                TitleAction::HighScores => next_screen.set(Screen::HighScores),
+                TitleAction::Settings => next_screen.set(Screen::Settings),
+                TitleAction::Replay => commands.add(WatchReplay),
                 TitleAction::Credits => next_screen.set(Screen::Credits),
 
                 #[cfg(not(target_family = "wasm"))]
@@ -54,3 +147,14 @@ fn handle_title_action(
         }
     }
 }
+
+fn select_difficulty(
+    mut difficulty: ResMut<Difficulty>,
+    selector_query: Query<&Cycle, (With<DifficultySelector>, Changed<Cycle>)>,
+) {
+    for cycle in &selector_query {
+        if let Some(&selected) = Difficulty::ALL.get(cycle.selected) {
+            difficulty.set_if_neq(selected);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Selectors that cycle through a list of options when clicked.
+//! Localized options are looked up again whenever the language or a string table changes.
+
+use bevy::prelude::*;
+
+use super::{
+    interaction::InteractionQuery,
+    localization::{Localizer, StringTable, UiText},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Cycle>();
+    app.add_systems(Update, (cycle_on_press, show_cycles).chain());
+}
+
+/// One option out of several.
+/// Spawned with [`Widgets::cycle`](super::widgets::Widgets::cycle).
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Cycle {
+    pub options: Vec<UiText>,
+    pub selected: usize,
+}
+
+impl Cycle {
+    pub fn new(options: impl IntoIterator<Item = impl Into<UiText>>, selected: usize) -> Self {
+        let options: Vec<UiText> = options.into_iter().map(Into::into).collect();
+        let selected = selected.min(options.len().saturating_sub(1));
+        Self { options, selected }
+    }
+
+    pub fn selected_option(&self) -> Option<&UiText> {
+        self.options.get(self.selected)
+    }
+
+    /// Selects the next option, wrapping around after the last.
+    pub fn next(&mut self) {
+        if !self.options.is_empty() {
+            self.selected = (self.selected + 1) % self.options.len();
+        }
+    }
+
+    /// Selects the previous option, wrapping around before the first.
+    pub fn previous(&mut self) {
+        if !self.options.is_empty() {
+            self.selected = (self.selected + self.options.len() - 1) % self.options.len();
+        }
+    }
+
+    fn text(&self, localizer: &Localizer) -> String {
+        let option = self.selected_option();
+        format!(
+            "< {} >",
+            option
+                .map(|option| localizer.text(option))
+                .unwrap_or_default()
+        )
+    }
+}
+
+fn cycle_on_press(mut cycle_query: InteractionQuery<&mut Cycle>) {
+    for (interaction, mut cycle) in &mut cycle_query {
+        if matches!(interaction, Interaction::Pressed) {
+            cycle.next();
+        }
+    }
+}
+
+fn show_cycles(
+    localizer: Localizer,
+    mut table_events: EventReader<AssetEvent<StringTable>>,
+    cycle_query: Query<(Ref<Cycle>, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let tables_changed = table_events.read().count() > 0;
+    let relocalize = tables_changed || localizer.language_changed();
+    for (cycle, children) in &cycle_query {
+        if !relocalize && !cycle.is_changed() {
+            continue;
+        }
+        let value = cycle.text(&localizer);
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            if text.sections[2].value != value {
+                text.sections[2].value = value.clone();
+            }
+        }
+    }
+}
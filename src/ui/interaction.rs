@@ -1,15 +1,16 @@
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<InteractionPalette>();
-    app.add_systems(Update, apply_interaction_palette);
+    app.register_type::<(InteractionPalette, Focused)>();
+    app.add_systems(Update, (focus_pressed, apply_interaction_palette));
 }
 
 pub type InteractionQuery<'w, 's, T> =
     Query<'w, 's, (&'static Interaction, T), Changed<Interaction>>;
 
 /// Palette for widget interactions.
-#[derive(Component, Debug, Reflect)]
+/// Themed widgets get theirs from the [`Theme`](super::theme::Theme).
+#[derive(Component, Debug, Default, Reflect)]
 #[reflect(Component)]
 pub struct InteractionPalette {
     pub none: Color,
@@ -17,15 +18,45 @@ pub struct InteractionPalette {
     pub pressed: Color,
 }
 
+/// The focused widget looks hovered.
 fn apply_interaction_palette(
-    mut palette_query: InteractionQuery<(&InteractionPalette, &mut BackgroundColor)>,
+    mut palette_query: Query<(
+        &Interaction,
+        Has<Focused>,
+        &InteractionPalette,
+        &mut BackgroundColor,
+    )>,
 ) {
-    for (interaction, (palette, mut background)) in &mut palette_query {
-        *background = match interaction {
-            Interaction::None => palette.none,
-            Interaction::Hovered => palette.hovered,
+    for (interaction, focused, palette, mut background) in &mut palette_query {
+        let color = match interaction {
             Interaction::Pressed => palette.pressed,
+            Interaction::Hovered => palette.hovered,
+            Interaction::None if focused => palette.hovered,
+            Interaction::None => palette.none,
+        };
+        background.set_if_neq(color.into());
+    }
+}
+
+/// Marks the widget that keyboard input goes to, like a [`TextInput`](super::text_input::TextInput),
+/// see [`navigation`](super::navigation). At most one widget is focused at a time.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Focused;
+
+/// Clicking a widget focuses it.
+fn focus_pressed(
+    mut commands: Commands,
+    interaction_query: Query<(Entity, &Interaction), Changed<Interaction>>,
+    focused_query: Query<Entity, With<Focused>>,
+) {
+    for (entity, interaction) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        for focused in &focused_query {
+            commands.entity(focused).remove::<Focused>();
         }
-        .into();
+        commands.entity(entity).insert(Focused);
     }
 }
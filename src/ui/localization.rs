@@ -0,0 +1,242 @@
+//! Showing text in the player's language.
+//! Every [`Language`] has a string table in `assets/locales/<code>.strings.ron` mapping keys
+//! to text, which may contain `{name}` placeholders for a [`Localized`] text's arguments.
+//! Keys missing from a table fall back to English, and then to the key itself.
+//! Texts with a [`LocalizedText`] are rewritten whenever the language or a table changes.
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    ecs::system::SystemParam,
+    prelude::*,
+    ui::{widget::measure_text_system, UiSystem},
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::settings::Settings;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<StringTable>();
+    app.register_asset_loader(StringTableLoader);
+    app.register_type::<(Language, LocalizedText)>();
+    let tables = StringTables::new(app.world().resource::<AssetServer>());
+    app.insert_resource(tables);
+    app.add_systems(
+        PostUpdate,
+        localize_texts
+            .before(measure_text_system)
+            .before(UiSystem::Layout),
+    );
+}
+
+/// The languages the game is translated into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Spanish,
+    Russian,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::German,
+        Language::Spanish,
+        Language::Russian,
+    ];
+
+    /// The language's ISO 639-1 code, which names its string table.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::Spanish => "es",
+            Language::Russian => "ru",
+        }
+    }
+
+    /// The language's name in itself, so that players can find theirs.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Spanish => "Español",
+            Language::Russian => "Русский",
+        }
+    }
+}
+
+/// A text to look up in the string tables, with arguments for its placeholders.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+// Arguments can be localized texts themselves.
+#[reflect(no_field_bounds)]
+pub struct Localized {
+    pub key: String,
+    pub args: Vec<(String, UiText)>,
+}
+
+impl Localized {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Fills in the `{name}` placeholder.
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<UiText>) -> Self {
+        self.args.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Text shown in the UI, either as it is or in the player's language.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(no_field_bounds)]
+pub enum UiText {
+    Literal(String),
+    Localized(Localized),
+}
+
+impl From<&str> for UiText {
+    fn from(text: &str) -> Self {
+        UiText::Literal(text.to_string())
+    }
+}
+
+impl From<String> for UiText {
+    fn from(text: String) -> Self {
+        UiText::Literal(text)
+    }
+}
+
+impl From<Localized> for UiText {
+    fn from(text: Localized) -> Self {
+        UiText::Localized(text)
+    }
+}
+
+/// Keeps a section of the entity's [`Text`] in the player's language.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct LocalizedText {
+    pub section: usize,
+    pub text: Localized,
+}
+
+impl LocalizedText {
+    pub fn new(text: Localized) -> Self {
+        Self { section: 0, text }
+    }
+}
+
+/// The keys and texts of one language.
+#[derive(Asset, TypePath, Debug, Deserialize, Deref)]
+#[serde(transparent)]
+pub struct StringTable(HashMap<String, String>);
+
+#[derive(Default)]
+struct StringTableLoader;
+
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<StringTable, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["strings.ron"]
+    }
+}
+
+/// The string tables of every language, loaded at startup.
+#[derive(Resource, Debug)]
+pub struct StringTables(HashMap<Language, Handle<StringTable>>);
+
+impl StringTables {
+    fn new(asset_server: &AssetServer) -> Self {
+        Self(
+            Language::ALL
+                .into_iter()
+                .map(|language| {
+                    let path = format!("locales/{}.strings.ron", language.code());
+                    (language, asset_server.load(path))
+                })
+                .collect(),
+        )
+    }
+
+    pub fn all_loaded(&self, assets: &Assets<StringTable>) -> bool {
+        self.0.values().all(|handle| assets.contains(handle))
+    }
+}
+
+/// Looks up texts in the player's language.
+#[derive(SystemParam)]
+pub struct Localizer<'w> {
+    settings: Res<'w, Settings>,
+    handles: Res<'w, StringTables>,
+    tables: Res<'w, Assets<StringTable>>,
+}
+
+impl Localizer<'_> {
+    pub fn get(&self, localized: &Localized) -> String {
+        let mut text = self.template(&localized.key).to_string();
+        for (name, value) in &localized.args {
+            text = text.replace(&format!("{{{name}}}"), &self.text(value));
+        }
+        text
+    }
+
+    pub fn text(&self, text: &UiText) -> String {
+        match text {
+            UiText::Literal(text) => text.clone(),
+            UiText::Localized(localized) => self.get(localized),
+        }
+    }
+
+    fn template<'a>(&'a self, key: &'a str) -> &'a str {
+        [self.settings.language, Language::English]
+            .into_iter()
+            .filter_map(|language| self.tables.get(&self.handles.0[&language]))
+            .find_map(|table| table.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// Whether the settings, and with them maybe the language, changed since the system last ran.
+    pub(super) fn language_changed(&self) -> bool {
+        self.settings.is_changed()
+    }
+}
+
+fn localize_texts(
+    localizer: Localizer,
+    mut table_events: EventReader<AssetEvent<StringTable>>,
+    mut text_query: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    let tables_changed = table_events.read().count() > 0;
+    let relocalize = tables_changed || localizer.language_changed();
+    for (localized, mut text) in &mut text_query {
+        if !relocalize && !localized.is_changed() {
+            continue;
+        }
+        let value = localizer.get(&localized.text);
+        if let Some(section) = text.sections.get_mut(localized.section) {
+            if section.value != value {
+                section.value = value;
+            }
+        }
+    }
+}
@@ -3,14 +3,28 @@
 // Unused utilities and re-exports may trigger these lints undesirably.
 #![allow(dead_code, unused_imports)]
 
+pub mod cycle;
 pub mod interaction;
-pub mod palette;
+pub mod localization;
+pub mod navigation;
+pub mod scroll;
+pub mod slider;
+pub mod text_input;
+pub mod theme;
+pub mod toggle;
 pub(crate) mod widgets;
 
 pub mod prelude {
     pub use super::{
-        interaction::{InteractionPalette, InteractionQuery},
-        palette as ui_palette,
+        cycle::Cycle,
+        interaction::{Focused, InteractionPalette, InteractionQuery},
+        localization::{Language, Localized, LocalizedText, Localizer, UiText},
+        navigation::BackButton,
+        scroll::ScrollList,
+        slider::Slider,
+        text_input::{CharacterSet, TextInput},
+        theme::Theme,
+        toggle::Toggle,
         widgets::{Containers as _, Widgets as _},
     };
 }
@@ -18,5 +32,15 @@ pub mod prelude {
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((
+        cycle::plugin,
+        interaction::plugin,
+        localization::plugin,
+        navigation::plugin,
+        scroll::plugin,
+        slider::plugin,
+        text_input::plugin,
+        theme::plugin,
+        toggle::plugin,
+    ));
 }
@@ -0,0 +1,172 @@
+//! Using menus without a mouse.
+//! The arrow keys or the D-pad move the [`Focused`] widget to the nearest one in that direction,
+//! and Enter or the gamepad's south button presses it.
+//! Sideways input adjusts a focused [`Slider`] instead, which is never pressed.
+//! Escape or the east button presses the screen's [`BackButton`].
+
+use bevy::{input::InputSystem, prelude::*, ui::UiSystem};
+
+use super::{
+    cycle::Cycle,
+    interaction::{Focused, InteractionPalette},
+    slider::Slider,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<BackButton>();
+    app.add_systems(Update, navigate);
+    // Run after the mouse had its say on interactions.
+    app.add_systems(
+        PreUpdate,
+        press_with_buttons.after(InputSystem).after(UiSystem::Focus),
+    );
+}
+
+/// Marks the button that takes players back from the screen it's on.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BackButton;
+
+/// How much sideways distance counts against a widget compared to distance in the direction
+/// moved in, so that moving down picks the widget below over one further down and to the side.
+const ACROSS_PENALTY: f32 = 2.0;
+
+fn any_gamepad_pressed(
+    gamepads: &Gamepads,
+    gamepad_input: &ButtonInput<GamepadButton>,
+    button: GamepadButtonType,
+) -> bool {
+    gamepads
+        .iter()
+        .any(|gamepad| gamepad_input.just_pressed(GamepadButton::new(gamepad, button)))
+}
+
+/// Which way players want to move focus, if any.
+fn focus_direction(
+    keyboard_input: &ButtonInput<KeyCode>,
+    gamepads: &Gamepads,
+    gamepad_input: &ButtonInput<GamepadButton>,
+) -> Option<Vec2> {
+    let pressed = |key: KeyCode, button: GamepadButtonType| {
+        keyboard_input.just_pressed(key) || any_gamepad_pressed(gamepads, gamepad_input, button)
+    };
+    // UI coordinates point down.
+    [
+        (KeyCode::ArrowUp, GamepadButtonType::DPadUp, Vec2::NEG_Y),
+        (KeyCode::ArrowDown, GamepadButtonType::DPadDown, Vec2::Y),
+        (KeyCode::ArrowLeft, GamepadButtonType::DPadLeft, Vec2::NEG_X),
+        (KeyCode::ArrowRight, GamepadButtonType::DPadRight, Vec2::X),
+    ]
+    .into_iter()
+    .find(|&(key, button, _)| pressed(key, button))
+    .map(|(_, _, direction)| direction)
+}
+
+fn navigate(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    widget_query: Query<(Entity, &GlobalTransform), (With<Interaction>, With<InteractionPalette>)>,
+    mut focused_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            Option<&mut Slider>,
+            Option<&mut Cycle>,
+        ),
+        With<Focused>,
+    >,
+) {
+    let Some(direction) = focus_direction(&keyboard_input, &gamepads, &gamepad_input) else {
+        return;
+    };
+    let position = |transform: &GlobalTransform| transform.translation().truncate();
+
+    let Ok((focused, transform, slider, cycle)) = focused_query.get_single_mut() else {
+        // Start from the top left.
+        let first = widget_query
+            .iter()
+            .min_by(|(_, a), (_, b)| {
+                let (a, b) = (position(a), position(b));
+                a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+            })
+            .map(|(entity, _)| entity);
+        if let Some(first) = first {
+            commands.entity(first).insert(Focused);
+        }
+        return;
+    };
+
+    // Sideways input adjusts widgets that hold a value.
+    if direction.x != 0.0 {
+        if let Some(mut slider) = slider {
+            slider.step_by(direction.x);
+            return;
+        }
+        if let Some(mut cycle) = cycle {
+            if direction.x > 0.0 {
+                cycle.next();
+            } else {
+                cycle.previous();
+            }
+            return;
+        }
+    }
+
+    let origin = position(transform);
+    let nearest = widget_query
+        .iter()
+        .filter(|&(entity, _)| entity != focused)
+        .filter_map(|(entity, transform)| {
+            let offset = position(transform) - origin;
+            let along = offset.dot(direction);
+            let across = offset.perp_dot(direction).abs();
+            (along > 0.0).then_some((entity, along + ACROSS_PENALTY * across))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity);
+    if let Some(nearest) = nearest {
+        commands.entity(focused).remove::<Focused>();
+        commands.entity(nearest).insert(Focused);
+    }
+}
+
+/// Presses widgets like a click would. The press lasts a single frame.
+/// Sliders are left alone, since a press drags them to wherever the mouse happens to be.
+fn press_with_buttons(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    mut last_pressed: Local<Option<Entity>>,
+    mut interaction_query: Query<&mut Interaction, Without<Slider>>,
+    focused_query: Query<Entity, With<Focused>>,
+    back_query: Query<Entity, With<BackButton>>,
+) {
+    if let Some(mut interaction) = last_pressed
+        .take()
+        .and_then(|entity| interaction_query.get_mut(entity).ok())
+    {
+        if *interaction == Interaction::Pressed {
+            *interaction = Interaction::None;
+        }
+    }
+
+    let pressed = |key: KeyCode, button: GamepadButtonType| {
+        keyboard_input.just_pressed(key) || any_gamepad_pressed(&gamepads, &gamepad_input, button)
+    };
+    let target = if pressed(KeyCode::Enter, GamepadButtonType::South) {
+        focused_query.get_single().ok()
+    } else if pressed(KeyCode::Escape, GamepadButtonType::East) {
+        back_query.get_single().ok()
+    } else {
+        None
+    };
+    let Some(target) = target else {
+        return;
+    };
+    if let Ok(mut interaction) = interaction_query.get_mut(target) {
+        *interaction = Interaction::Pressed;
+        *last_pressed = Some(target);
+    }
+}
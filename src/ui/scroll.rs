@@ -0,0 +1,79 @@
+//! Lists taller than the space they have, scrolled with the mouse wheel.
+
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::RelativeCursorPosition,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ScrollList>();
+    app.add_systems(
+        Update,
+        (scroll_with_mouse_wheel, apply_scroll_offset).chain(),
+    );
+}
+
+/// How far one line of mouse wheel scrolling moves a list, in pixels.
+const LINE_HEIGHT: f32 = 30.0;
+
+/// How far a list is scrolled down, in pixels.
+/// Spawned with [`Widgets::scroll_list`](super::widgets::Widgets::scroll_list).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct ScrollList {
+    pub offset: f32,
+}
+
+impl ScrollList {
+    pub fn scroll_by(&mut self, pixels: f32) {
+        self.offset = (self.offset + pixels).max(0.0);
+    }
+}
+
+fn scroll_with_mouse_wheel(
+    mut wheel_input: EventReader<MouseWheel>,
+    mut list_query: Query<(&RelativeCursorPosition, &mut ScrollList)>,
+) {
+    for event in wheel_input.read() {
+        let pixels = match event.unit {
+            MouseScrollUnit::Line => event.y * LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        for (cursor, mut list) in &mut list_query {
+            if cursor.mouse_over() {
+                list.scroll_by(-pixels);
+            }
+        }
+    }
+}
+
+/// Moves the list's items up by its offset, without scrolling past the last one.
+fn apply_scroll_offset(
+    mut list_query: Query<(&mut ScrollList, &Node, &Style, &Children)>,
+    mut item_query: Query<(&Node, &mut Style), Without<ScrollList>>,
+) {
+    for (mut list, node, style, children) in &mut list_query {
+        let gap = match style.row_gap {
+            Val::Px(gap) => gap,
+            _ => 0.0,
+        };
+        let content_height = item_query
+            .iter_many(children)
+            .map(|(item, _)| item.size().y + gap)
+            .sum::<f32>()
+            - gap;
+        let max_offset = (content_height - node.size().y).max(0.0);
+        if list.offset > max_offset {
+            list.offset = max_offset;
+        }
+
+        let top = Val::Px(-list.offset);
+        let mut items = item_query.iter_many_mut(children);
+        while let Some((_, mut item_style)) = items.fetch_next() {
+            if item_style.top != top {
+                item_style.top = top;
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Sliders for picking a number from a range by dragging along a track.
+
+use bevy::{prelude::*, ui::RelativeCursorPosition};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Slider>();
+    app.add_systems(Update, (drag_sliders, show_sliders).chain());
+}
+
+/// A number between `min` and `max`, in multiples of `step` from `min`.
+/// Spawned with [`Widgets::slider`](super::widgets::Widgets::slider).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Slider {
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+}
+
+impl Slider {
+    pub fn new(value: f32, min: f32, max: f32, step: f32) -> Self {
+        Self {
+            value: value.clamp(min, max),
+            min,
+            max,
+            step,
+        }
+    }
+
+    /// How far along the track the value is, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f32 {
+        if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        }
+    }
+
+    /// The value `fraction` of the way along the track, snapped to a step.
+    pub fn value_at(&self, fraction: f32) -> f32 {
+        self.snap(self.min + fraction.clamp(0.0, 1.0) * (self.max - self.min))
+    }
+
+    /// Moves the value by `steps` steps, staying within the range.
+    pub fn step_by(&mut self, steps: f32) {
+        self.value = self.snap(self.value + steps * self.step);
+    }
+
+    /// The step closest to `value`, counted from `min` so that rounding errors don't add up.
+    fn snap(&self, value: f32) -> f32 {
+        let value = if self.step > 0.0 {
+            self.min + ((value - self.min) / self.step).round() * self.step
+        } else {
+            value
+        };
+        value.clamp(self.min, self.max)
+    }
+
+    pub(super) fn text(&self) -> String {
+        format!("{:.*}", self.decimals(), self.value)
+    }
+
+    /// As many decimals as the step has, like two for `0.25`.
+    fn decimals(&self) -> usize {
+        const MAX_DECIMALS: usize = 4;
+        if self.step <= 0.0 {
+            return 2;
+        }
+        (0..MAX_DECIMALS)
+            .find(|&decimals| {
+                let scaled = self.step * 10f32.powi(decimals as i32);
+                (scaled - scaled.round()).abs() < 1e-3
+            })
+            .unwrap_or(MAX_DECIMALS)
+    }
+}
+
+/// The part of the track up to the value.
+#[derive(Component)]
+pub(super) struct SliderFill;
+
+fn drag_sliders(mut slider_query: Query<(&Interaction, &RelativeCursorPosition, &mut Slider)>) {
+    for (interaction, cursor, mut slider) in &mut slider_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else {
+            continue;
+        };
+        let value = slider.value_at(position.x);
+        if slider.value != value {
+            slider.value = value;
+        }
+    }
+}
+
+fn show_sliders(
+    slider_query: Query<(&Slider, &Children), Changed<Slider>>,
+    mut fill_query: Query<&mut Style, With<SliderFill>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (slider, children) in &slider_query {
+        let mut fills = fill_query.iter_many_mut(children);
+        while let Some(mut style) = fills.fetch_next() {
+            style.width = Val::Percent(100.0 * slider.fraction());
+        }
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[2].value = slider.text();
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Text fields that take typing while they are [`Focused`].
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use super::interaction::Focused;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(TextInput, CharacterSet)>();
+    app.add_systems(Update, (type_into_text_inputs, show_text_inputs).chain());
+}
+
+/// How long the caret stays visible, and then hidden, while blinking.
+const CARET_BLINK_SECS: f32 = 0.5;
+
+/// Text typed by the player.
+/// Spawned with [`Widgets::text_input`](super::widgets::Widgets::text_input).
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct TextInput {
+    pub value: String,
+    pub max_length: usize,
+    pub allowed: CharacterSet,
+}
+
+impl TextInput {
+    pub fn new(value: impl Into<String>, max_length: usize, allowed: CharacterSet) -> Self {
+        Self {
+            value: value.into(),
+            max_length,
+            allowed,
+        }
+    }
+}
+
+/// Which characters a [`TextInput`] accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CharacterSet {
+    Any,
+    Digits,
+    /// ASCII letters and digits, plus the given punctuation.
+    Alphanumeric(&'static str),
+}
+
+impl CharacterSet {
+    pub fn allows(self, character: char) -> bool {
+        match self {
+            CharacterSet::Any => !character.is_control(),
+            CharacterSet::Digits => character.is_ascii_digit(),
+            CharacterSet::Alphanumeric(punctuation) => {
+                character.is_ascii_alphanumeric() || punctuation.contains(character)
+            }
+        }
+    }
+}
+
+fn type_into_text_inputs(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut input_query: Query<&mut TextInput, With<Focused>>,
+) {
+    let Ok(mut input) = input_query.get_single_mut() else {
+        keyboard_input.clear();
+        return;
+    };
+    for event in keyboard_input.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let typed = match &event.logical_key {
+            Key::Character(characters) => characters.as_str(),
+            Key::Space => " ",
+            Key::Backspace => {
+                input.value.pop();
+                continue;
+            }
+            _ => continue,
+        };
+        let allowed = input.allowed;
+        for character in typed.chars().filter(|&character| allowed.allows(character)) {
+            if input.value.chars().count() < input.max_length {
+                input.value.push(character);
+            }
+        }
+    }
+}
+
+fn show_text_inputs(
+    time: Res<Time>,
+    input_query: Query<(&TextInput, Has<Focused>, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let caret_visible = time.elapsed_seconds() % (2.0 * CARET_BLINK_SECS) < CARET_BLINK_SECS;
+    for (input, focused, children) in &input_query {
+        let caret = if focused && caret_visible { "|" } else { " " };
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            if text.sections[0].value != input.value {
+                text.sections[0].value.clone_from(&input.value);
+            }
+            if text.sections[1].value != caret {
+                text.sections[1].value = caret.to_string();
+            }
+        }
+    }
+}
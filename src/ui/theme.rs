@@ -0,0 +1,283 @@
+//! The look of menus: colors, fonts and sizes, loaded from `assets/ui.theme.ron`.
+//! Widgets are spawned with a [`ThemedNode`] or [`ThemedText`] role and styled from the
+//! [`Theme`] resource, again whenever it changes. With asset hot reloading, saving the file
+//! restyles the menus on screen.
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    ui::{widget::measure_text_system, UiSystem},
+};
+use serde::{Deserialize, Deserializer};
+
+use super::interaction::InteractionPalette;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Theme>();
+    app.register_asset_loader(ThemeLoader);
+    app.register_type::<(Theme, ThemedNode, ThemedText)>();
+    app.init_resource::<Theme>();
+    let handle = ThemeHandle(app.world().resource::<AssetServer>().load(THEME_PATH));
+    app.insert_resource(handle);
+    app.add_systems(
+        PostUpdate,
+        (use_loaded_theme, (style_nodes, style_texts))
+            .chain()
+            .before(measure_text_system)
+            .before(UiSystem::Layout),
+    );
+}
+
+const THEME_PATH: &str = "ui.theme.ron";
+
+/// How menus look. Starts out as [`Theme::default`] and is replaced once the theme file loads.
+#[derive(Asset, Resource, Debug, Clone, Deserialize, Reflect)]
+#[reflect(Resource)]
+pub struct Theme {
+    /// Space between widgets stacked in a column.
+    pub gap: f32,
+    /// Space between a widget's edges and its text.
+    pub padding: f32,
+    pub button_width: f32,
+    /// The width of headers, labels, and widgets that show a value.
+    pub wide_width: f32,
+    pub widget_height: f32,
+    pub label_height: f32,
+    #[serde(deserialize_with = "hex_color")]
+    pub node_background: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub button_hovered_background: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub button_pressed_background: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub slider_fill: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub text_input_background: Color,
+    pub button_text: TextTheme,
+    pub header_text: TextTheme,
+    pub label_text: TextTheme,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let text = |size, color| TextTheme {
+            font_path: None,
+            font: Handle::default(),
+            size,
+            color,
+        };
+        Self {
+            gap: 10.0,
+            padding: 0.0,
+            button_width: 200.0,
+            wide_width: 500.0,
+            widget_height: 65.0,
+            label_height: 30.0,
+            node_background: Color::srgb_u8(0x49, 0x7a, 0xc5),
+            button_hovered_background: Color::srgb_u8(0x2f, 0x54, 0x92),
+            button_pressed_background: Color::srgb_u8(0x49, 0x7a, 0xc5),
+            slider_fill: Color::srgb_u8(0x2f, 0x54, 0x92),
+            text_input_background: Color::srgb_u8(0x28, 0x28, 0x28),
+            button_text: text(32.0, Color::srgb_u8(0xec, 0xec, 0xec)),
+            header_text: text(48.0, Color::srgb_u8(0xdd, 0xd3, 0x69)),
+            label_text: text(16.0, Color::srgb_u8(0xdd, 0xd3, 0x69)),
+        }
+    }
+}
+
+/// How one kind of text looks.
+#[derive(Debug, Clone, Deserialize, Reflect)]
+pub struct TextTheme {
+    /// Where the font is, relative to `assets`. Bevy's built-in font if unset.
+    #[serde(default, rename = "font")]
+    pub font_path: Option<String>,
+    #[serde(skip)]
+    pub font: Handle<Font>,
+    pub size: f32,
+    #[serde(deserialize_with = "hex_color")]
+    pub color: Color,
+}
+
+impl TextTheme {
+    pub fn style(&self) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size: self.size,
+            color: self.color,
+        }
+    }
+}
+
+/// Reads colors written like `"#2f5492"`.
+fn hex_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    Srgba::hex(&hex)
+        .map(Color::from)
+        .map_err(|error| serde::de::Error::custom(format!("`{hex}`: {error}")))
+}
+
+#[derive(Default)]
+struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Theme, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut theme: Theme = ron::de::from_bytes(&bytes)?;
+        for text in [
+            &mut theme.button_text,
+            &mut theme.header_text,
+            &mut theme.label_text,
+        ] {
+            if let Some(path) = &text.font_path {
+                text.font = load_context.load(path);
+            }
+        }
+        Ok(theme)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct ThemeHandle(Handle<Theme>);
+
+/// Makes the theme file's contents current, when it first loads and whenever it changes.
+fn use_loaded_theme(
+    mut commands: Commands,
+    mut theme_events: EventReader<AssetEvent<Theme>>,
+    handle: Res<ThemeHandle>,
+    themes: Res<Assets<Theme>>,
+) {
+    for event in theme_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = *event
+        else {
+            continue;
+        };
+        if id != handle.0.id() {
+            continue;
+        }
+        if let Some(theme) = themes.get(id) {
+            commands.insert_resource(theme.clone());
+        }
+    }
+}
+
+/// Which part of the [`Theme`] a UI node's size and colors come from.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum ThemedNode {
+    Root,
+    Button,
+    Header,
+    Label,
+    /// A widget that shows a value next to its label, like a slider.
+    Widget,
+    TextInput,
+    SliderFill,
+    ScrollList,
+}
+
+/// Which part of the [`Theme`] a text's font, size and color come from.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum ThemedText {
+    Button,
+    Header,
+    Label,
+}
+
+fn style_nodes(
+    theme: Res<Theme>,
+    mut node_query: Query<(
+        Ref<ThemedNode>,
+        &mut Style,
+        Option<&mut BackgroundColor>,
+        Option<&mut InteractionPalette>,
+    )>,
+) {
+    let button_size = |width| (Val::Px(width), Val::Px(theme.widget_height));
+    for (role, mut style, background, palette) in &mut node_query {
+        if !theme.is_changed() && !role.is_added() {
+            continue;
+        }
+        let padding = UiRect::horizontal(Val::Px(theme.padding));
+        let background_color = match *role {
+            ThemedNode::Root => {
+                style.row_gap = Val::Px(theme.gap);
+                None
+            }
+            ThemedNode::Button => {
+                (style.width, style.height) = button_size(theme.button_width);
+                style.padding = padding;
+                Some(theme.node_background)
+            }
+            ThemedNode::Header => {
+                (style.width, style.height) = button_size(theme.wide_width);
+                style.padding = padding;
+                Some(theme.node_background)
+            }
+            ThemedNode::Label => {
+                style.width = Val::Px(theme.wide_width);
+                style.height = Val::Px(theme.label_height);
+                None
+            }
+            ThemedNode::Widget => {
+                (style.width, style.height) = button_size(theme.wide_width);
+                style.padding = padding;
+                Some(theme.node_background)
+            }
+            ThemedNode::TextInput => {
+                (style.width, style.height) = button_size(theme.wide_width);
+                style.padding = padding;
+                Some(theme.text_input_background)
+            }
+            ThemedNode::SliderFill => Some(theme.slider_fill),
+            ThemedNode::ScrollList => {
+                style.width = Val::Px(theme.wide_width);
+                style.row_gap = Val::Px(theme.gap);
+                None
+            }
+        };
+        let Some(color) = background_color else {
+            continue;
+        };
+        if let Some(mut background) = background {
+            background.0 = color;
+        }
+        if let Some(mut palette) = palette {
+            *palette = InteractionPalette {
+                none: color,
+                hovered: theme.button_hovered_background,
+                pressed: theme.button_pressed_background,
+            };
+        }
+    }
+}
+
+fn style_texts(theme: Res<Theme>, mut text_query: Query<(Ref<ThemedText>, &mut Text)>) {
+    for (role, mut text) in &mut text_query {
+        if !theme.is_changed() && !role.is_added() {
+            continue;
+        }
+        let style = match *role {
+            ThemedText::Button => theme.button_text.style(),
+            ThemedText::Header => theme.header_text.style(),
+            ThemedText::Label => theme.label_text.style(),
+        };
+        for section in &mut text.sections {
+            section.style = style.clone();
+        }
+    }
+}
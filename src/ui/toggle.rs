@@ -0,0 +1,46 @@
+//! Checkboxes that switch something on or off when clicked.
+
+use bevy::prelude::*;
+
+use super::interaction::InteractionQuery;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Toggle>();
+    app.add_systems(Update, (flip_toggles, show_toggles).chain());
+}
+
+/// Whether a checkbox is checked.
+/// Spawned with [`Widgets::toggle`](super::widgets::Widgets::toggle).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Toggle(pub bool);
+
+impl Toggle {
+    pub(super) fn text(self) -> &'static str {
+        if self.0 {
+            "[x] "
+        } else {
+            "[ ] "
+        }
+    }
+}
+
+fn flip_toggles(mut toggle_query: InteractionQuery<&mut Toggle>) {
+    for (interaction, mut toggle) in &mut toggle_query {
+        if matches!(interaction, Interaction::Pressed) {
+            toggle.0 = !toggle.0;
+        }
+    }
+}
+
+fn show_toggles(
+    toggle_query: Query<(&Toggle, &Children), Changed<Toggle>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (toggle, children) in &toggle_query {
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[0].value = toggle.text().to_string();
+        }
+    }
+}
@@ -1,8 +1,21 @@
 //! Helper traits for creating common widgets.
 
-use bevy::{ecs::system::EntityCommands, prelude::*, ui::Val::*};
+use bevy::{
+    ecs::system::EntityCommands,
+    prelude::*,
+    ui::{RelativeCursorPosition, Val::*},
+};
 
-use super::{interaction::InteractionPalette, palette::*};
+use super::{
+    cycle::Cycle,
+    interaction::InteractionPalette,
+    localization::{LocalizedText, UiText},
+    scroll::ScrollList,
+    slider::{Slider, SliderFill},
+    text_input::TextInput,
+    theme::{ThemedNode, ThemedText},
+    toggle::Toggle,
+};
 
 /// An internal trait for types that can spawn entities.
 trait Spawn {
@@ -21,6 +34,15 @@ impl Spawn for ChildBuilder<'_> {
     }
 }
 
+/// Splits text into what a [`Text`] section starts out with and, if the text is localized,
+/// what keeps the section in the player's language.
+fn section_text(text: impl Into<UiText>, section: usize) -> (String, Option<LocalizedText>) {
+    match text.into() {
+        UiText::Literal(text) => (text, None),
+        UiText::Localized(text) => (String::new(), Some(LocalizedText { section, text })),
+    }
+}
+
 /// An extension trait for spawning UI containers.
 pub trait Containers {
     /// Spawns a root node that covers the full screen
@@ -39,12 +61,12 @@ impl Containers for Commands<'_, '_> {
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     flex_direction: FlexDirection::Column,
-                    row_gap: Px(10.0),
                     position_type: PositionType::Absolute,
                     ..default()
                 },
                 ..default()
             },
+            ThemedNode::Root,
         ))
     }
 }
@@ -52,110 +74,276 @@ impl Containers for Commands<'_, '_> {
 /// An extension trait for spawning UI widgets.
 pub trait Widgets {
     /// Spawn a simple button with text.
-    fn button(&mut self, text: impl Into<String>) -> EntityCommands;
+    fn button(&mut self, text: impl Into<UiText>) -> EntityCommands;
 
     /// Spawn a simple header label. Bigger than [`Widgets::label`].
-    fn header(&mut self, text: impl Into<String>) -> EntityCommands;
+    fn header(&mut self, text: impl Into<UiText>) -> EntityCommands;
 
     /// Spawn a simple text label.
-    fn label(&mut self, text: impl Into<String>) -> EntityCommands;
+    fn label(&mut self, text: impl Into<UiText>) -> EntityCommands;
+
+    /// Spawn a track that can be dragged along to pick a number, see [`Slider`].
+    fn slider(&mut self, label: impl Into<UiText>, slider: Slider) -> EntityCommands;
+
+    /// Spawn a checkbox, see [`Toggle`].
+    fn toggle(&mut self, label: impl Into<UiText>, on: bool) -> EntityCommands;
+
+    /// Spawn a selector that cycles through options when clicked, see [`Cycle`].
+    fn cycle(&mut self, label: impl Into<UiText>, cycle: Cycle) -> EntityCommands;
+
+    /// Spawn a field to type text into once it is clicked, see [`TextInput`].
+    fn text_input(&mut self, input: TextInput) -> EntityCommands;
+
+    /// Spawn a list of the given height that scrolls when it's too short for its children,
+    /// see [`ScrollList`].
+    fn scroll_list(&mut self, height: Val) -> EntityCommands;
 }
 
 impl<T: Spawn> Widgets for T {
-    fn button(&mut self, text: impl Into<String>) -> EntityCommands {
+    fn button(&mut self, text: impl Into<UiText>) -> EntityCommands {
+        let (value, localized) = section_text(text, 0);
         let mut entity = self.spawn((
             Name::new("Button"),
             ButtonBundle {
                 style: Style {
-                    width: Px(200.0),
-                    height: Px(65.0),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
                 },
-                background_color: BackgroundColor(NODE_BACKGROUND),
                 ..default()
             },
-            InteractionPalette {
-                none: NODE_BACKGROUND,
-                hovered: BUTTON_HOVERED_BACKGROUND,
-                pressed: BUTTON_PRESSED_BACKGROUND,
-            },
+            InteractionPalette::default(),
+            ThemedNode::Button,
         ));
         entity.with_children(|children| {
-            children.spawn((
+            let mut text = children.spawn((
                 Name::new("Button Text"),
-                TextBundle::from_section(
-                    text,
-                    TextStyle {
-                        font_size: 40.0,
-                        color: BUTTON_TEXT,
-                        ..default()
-                    },
-                ),
+                TextBundle::from_section(value, TextStyle::default()),
+                ThemedText::Button,
             ));
+            if let Some(localized) = localized {
+                text.insert(localized);
+            }
         });
         entity
     }
 
-    fn header(&mut self, text: impl Into<String>) -> EntityCommands {
+    fn header(&mut self, text: impl Into<UiText>) -> EntityCommands {
+        let (value, localized) = section_text(text, 0);
         let mut entity = self.spawn((
             Name::new("Header"),
             NodeBundle {
                 style: Style {
-                    width: Px(500.0),
-                    height: Px(65.0),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
                 },
-                background_color: BackgroundColor(NODE_BACKGROUND),
                 ..default()
             },
+            ThemedNode::Header,
         ));
         entity.with_children(|children| {
-            children.spawn((
+            let mut text = children.spawn((
                 Name::new("Header Text"),
-                TextBundle::from_section(
-                    text,
-                    TextStyle {
-                        font_size: 40.0,
-                        color: HEADER_TEXT,
-                        ..default()
-                    },
-                ),
+                TextBundle::from_section(value, TextStyle::default()),
+                ThemedText::Header,
             ));
+            if let Some(localized) = localized {
+                text.insert(localized);
+            }
         });
         entity
     }
 
-    fn label(&mut self, text: impl Into<String>) -> EntityCommands {
+    fn label(&mut self, text: impl Into<UiText>) -> EntityCommands {
+        let (value, localized) = section_text(text, 0);
         let mut entity = self.spawn((
             Name::new("Label"),
             NodeBundle {
                 style: Style {
-                    width: Px(500.0),
-                    height: Px(30.0),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
                 },
                 ..default()
             },
+            ThemedNode::Label,
         ));
         entity.with_children(|children| {
-            children.spawn((
+            let mut text = children.spawn((
                 Name::new("Label Text"),
-                TextBundle::from_section(
-                    text,
-                    TextStyle {
-                        font_size: 24.0,
-                        color: LABEL_TEXT,
+                TextBundle::from_section(value, TextStyle::default()),
+                ThemedText::Label,
+            ));
+            if let Some(localized) = localized {
+                text.insert(localized);
+            }
+        });
+        entity
+    }
+
+    fn slider(&mut self, label: impl Into<UiText>, slider: Slider) -> EntityCommands {
+        let (label, localized) = section_text(label, 0);
+        let mut entity = self.spawn((
+            Name::new("Slider"),
+            ButtonBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            InteractionPalette::default(),
+            ThemedNode::Widget,
+            RelativeCursorPosition::default(),
+            slider,
+        ));
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Slider Fill"),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Px(0.0),
+                        width: Percent(100.0 * slider.fraction()),
+                        height: Percent(100.0),
                         ..default()
                     },
-                ),
+                    ..default()
+                },
+                SliderFill,
+                ThemedNode::SliderFill,
+            ));
+            let mut text = children.spawn((
+                Name::new("Slider Text"),
+                TextBundle::from_sections([
+                    TextSection::new(label, TextStyle::default()),
+                    TextSection::new(": ", TextStyle::default()),
+                    TextSection::new(slider.text(), TextStyle::default()),
+                ]),
+                ThemedText::Button,
             ));
+            if let Some(localized) = localized {
+                text.insert(localized);
+            }
         });
         entity
     }
+
+    fn toggle(&mut self, label: impl Into<UiText>, on: bool) -> EntityCommands {
+        let (label, localized) = section_text(label, 1);
+        let toggle = Toggle(on);
+        let mut entity = self.spawn((
+            Name::new("Toggle"),
+            ButtonBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            InteractionPalette::default(),
+            ThemedNode::Widget,
+            toggle,
+        ));
+        entity.with_children(|children| {
+            let mut text = children.spawn((
+                Name::new("Toggle Text"),
+                TextBundle::from_sections([
+                    TextSection::new(toggle.text(), TextStyle::default()),
+                    TextSection::new(label, TextStyle::default()),
+                ]),
+                ThemedText::Button,
+            ));
+            if let Some(localized) = localized {
+                text.insert(localized);
+            }
+        });
+        entity
+    }
+
+    fn cycle(&mut self, label: impl Into<UiText>, cycle: Cycle) -> EntityCommands {
+        let (label, localized) = section_text(label, 0);
+        let mut entity = self.spawn((
+            Name::new("Cycle"),
+            ButtonBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            InteractionPalette::default(),
+            ThemedNode::Widget,
+            cycle,
+        ));
+        entity.with_children(|children| {
+            let mut text = children.spawn((
+                Name::new("Cycle Text"),
+                TextBundle::from_sections([
+                    TextSection::new(label, TextStyle::default()),
+                    TextSection::new(": ", TextStyle::default()),
+                    // Filled in with the selected option once it's looked up.
+                    TextSection::new(String::new(), TextStyle::default()),
+                ]),
+                ThemedText::Button,
+            ));
+            if let Some(localized) = localized {
+                text.insert(localized);
+            }
+        });
+        entity
+    }
+
+    fn text_input(&mut self, input: TextInput) -> EntityCommands {
+        let value = input.value.clone();
+        let mut entity = self.spawn((
+            Name::new("Text Input"),
+            ButtonBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            InteractionPalette::default(),
+            ThemedNode::TextInput,
+            input,
+        ));
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Text Input Text"),
+                TextBundle::from_sections([
+                    TextSection::new(value, TextStyle::default()),
+                    // The caret.
+                    TextSection::new(" ", TextStyle::default()),
+                ]),
+                ThemedText::Button,
+            ));
+        });
+        entity
+    }
+
+    fn scroll_list(&mut self, height: Val) -> EntityCommands {
+        self.spawn((
+            Name::new("Scroll List"),
+            NodeBundle {
+                style: Style {
+                    height,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    overflow: Overflow::clip_y(),
+                    ..default()
+                },
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            ScrollList::default(),
+            ThemedNode::ScrollList,
+        ))
+    }
 }
@@ -0,0 +1,140 @@
+//! The credits roll, running headless, see [`bevy_quickstart::headless`].
+
+use std::{fs, path::Path, time::Duration};
+
+use bevy::prelude::*;
+use bevy_quickstart::headless::{Game, Screen};
+use serde::Deserialize;
+
+/// Just enough of `assets/game.credits.ron` to check the licenses.
+#[derive(Deserialize)]
+struct CreditsFile {
+    sections: Vec<Section>,
+}
+
+#[derive(Deserialize)]
+struct Section {
+    heading: String,
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    name: String,
+    #[serde(default)]
+    license: Option<String>,
+}
+
+/// A game that made it to the credits screen.
+fn credits() -> Game {
+    let mut game = Game::at_title();
+    game.click("Credits");
+    assert_eq!(game.screen(), Screen::Credits);
+    game
+}
+
+/// Every piece of text on screen.
+fn texts(game: &mut Game) -> Vec<String> {
+    let world = game.app().world_mut();
+    world
+        .query::<&Text>()
+        .iter(world)
+        .map(|text| {
+            text.sections
+                .iter()
+                .map(|section| &*section.value)
+                .collect()
+        })
+        .collect()
+}
+
+/// How far the credits are from the top of the area they roll through.
+fn roll_position(game: &mut Game) -> f32 {
+    let world = game.app().world_mut();
+    let (_, style) = world
+        .query::<(&Name, &Style)>()
+        .iter(world)
+        .find(|(name, _)| name.as_str() == "Credits")
+        .unwrap();
+    match style.top {
+        Val::Px(top) => top,
+        top => panic!("The credits are at {top:?}"),
+    }
+}
+
+#[test]
+fn the_credits_come_from_the_credits_file() {
+    let mut game = credits();
+    let texts = texts(&mut game);
+    for text in [
+        "Made by",
+        "Music",
+        "Ducky sprite",
+        "CC0 by Caz Creates Games",
+        "CC0 by the Bevy Foundation",
+        "CC0 by NOX SOUND",
+        "\"Fluffing A Duck\" - Gameplay",
+    ] {
+        assert!(texts.contains(&text.to_string()), "{text:?} in {texts:?}");
+    }
+}
+
+#[test]
+fn every_asset_in_the_credits_has_a_license() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/game.credits.ron");
+    let credits: CreditsFile = ron::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    let assets: Vec<_> = credits
+        .sections
+        .iter()
+        .filter(|section| ["graphics", "music", "sound-effects"].contains(&&*section.heading))
+        .flat_map(|section| &section.entries)
+        .collect();
+    assert!(!assets.is_empty());
+    for entry in assets {
+        assert!(entry.license.is_some(), "{:?} has no license", entry.name);
+    }
+    // Neither had a known author or license, so they're no longer shipped.
+    for (name, file) in [
+        ("SMS Brawler tile sets", "assets/images/tilesets"),
+        (
+            "Vigilante sprite sheet",
+            "assets/images/Vigilante_Free_SpriteSheet.png",
+        ),
+    ] {
+        assert!(!credits
+            .sections
+            .iter()
+            .flat_map(|section| &section.entries)
+            .any(|entry| entry.name == name));
+        assert!(!Path::new(env!("CARGO_MANIFEST_DIR")).join(file).exists());
+    }
+}
+
+#[test]
+fn the_credits_roll_faster_while_space_is_held() {
+    let mut game = credits();
+    let start = roll_position(&mut game);
+    game.run_for(Duration::from_secs(1));
+    let rolled = start - roll_position(&mut game);
+    assert!(rolled > 0.0);
+
+    game.press(KeyCode::Space);
+    let start = roll_position(&mut game);
+    game.run_for(Duration::from_secs(1));
+    let sped_up = start - roll_position(&mut game);
+    assert!(sped_up > 3.0 * rolled, "{sped_up} vs. {rolled}");
+
+    game.release(KeyCode::Space);
+    let start = roll_position(&mut game);
+    game.run_for(Duration::from_secs(1));
+    let slowed_down = start - roll_position(&mut game);
+    assert!(slowed_down < 1.5 * rolled, "{slowed_down} vs. {rolled}");
+}
+
+#[test]
+fn back_skips_the_credits() {
+    let mut game = credits();
+    game.run_for(Duration::from_secs(1));
+    game.click("Back");
+    assert_eq!(game.screen(), Screen::Title);
+}
@@ -0,0 +1,60 @@
+//! Which fonts text is drawn with, running headless, see [`bevy_quickstart::headless`].
+
+use std::{thread, time::Duration};
+
+use bevy::prelude::*;
+use bevy_quickstart::headless::{Game, Screen, Theme};
+
+const PIXEL_FONT: &str = "fonts/brawler_pixel.ttf";
+
+/// A game that made it to the title screen, with the theme file loaded.
+fn title_with_theme() -> Game {
+    let mut game = Game::at_title();
+
+    let pixel_font = pixel_font(&mut game);
+    for _ in 0..200 {
+        if game.app().world().resource::<Theme>().button_text.font == pixel_font {
+            break;
+        }
+        game.update();
+        thread::sleep(Duration::from_millis(10));
+    }
+    game
+}
+
+fn pixel_font(game: &mut Game) -> Handle<Font> {
+    game.app()
+        .world()
+        .resource::<AssetServer>()
+        .load(PIXEL_FONT)
+}
+
+/// The fonts of every piece of text on screen.
+fn fonts(game: &mut Game) -> Vec<AssetId<Font>> {
+    let world = game.app().world_mut();
+    world
+        .query::<&Text>()
+        .iter(world)
+        .flat_map(|text| text.sections.iter().map(|section| section.style.font.id()))
+        .collect()
+}
+
+#[test]
+fn menus_use_the_pixel_font() {
+    let mut game = title_with_theme();
+    let pixel_font = pixel_font(&mut game).id();
+    let fonts = fonts(&mut game);
+    assert!(!fonts.is_empty());
+    assert!(fonts.iter().all(|&font| font == pixel_font));
+}
+
+#[test]
+fn the_hud_uses_the_pixel_font() {
+    let mut game = title_with_theme();
+    game.click("Play");
+    assert_eq!(game.screen(), Screen::Playing);
+    let pixel_font = pixel_font(&mut game).id();
+    let fonts = fonts(&mut game);
+    assert!(!fonts.is_empty());
+    assert!(fonts.iter().all(|&font| font == pixel_font));
+}
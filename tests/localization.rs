@@ -0,0 +1,127 @@
+//! Showing the game in the player's language, running headless,
+//! see [`bevy_quickstart::headless`].
+
+use std::{collections::BTreeSet, fs, path::Path, thread, time::Duration};
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_quickstart::headless::{Game, Language, Screen, Settings};
+
+/// The keys of a language's string table.
+fn keys(language: Language) -> BTreeSet<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets/locales")
+        .join(format!("{}.strings.ron", language.code()));
+    let data = fs::read_to_string(&path).unwrap();
+    let table: HashMap<String, String> = ron::from_str(&data).unwrap();
+    table.into_keys().collect()
+}
+
+/// Every piece of text on screen.
+fn texts(game: &mut Game) -> Vec<String> {
+    let world = game.app().world_mut();
+    world
+        .query::<&Text>()
+        .iter(world)
+        .map(|text| {
+            text.sections
+                .iter()
+                .map(|section| &*section.value)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn every_language_translates_every_text() {
+    let english = keys(Language::English);
+    for language in Language::ALL {
+        assert_eq!(keys(language), english, "{language:?}");
+    }
+}
+
+#[test]
+fn the_settings_screen_switches_the_language() {
+    let mut game = Game::at_title();
+    assert!(game.buttons().contains(&"Play".to_string()));
+
+    game.click("Settings");
+    assert_eq!(game.screen(), Screen::Settings);
+    game.click("Language: < English >");
+    game.update();
+    assert_eq!(
+        game.app().world().resource::<Settings>().language,
+        Language::German
+    );
+    // The screen follows right away.
+    let buttons = game.buttons();
+    assert!(buttons.contains(&"Sprache: < Deutsch >".to_string()));
+    assert!(buttons.contains(&"Zurück".to_string()));
+
+    game.click("Zurück");
+    assert_eq!(game.screen(), Screen::Title);
+    let buttons = game.buttons();
+    assert!(buttons.contains(&"Spielen".to_string()));
+    assert!(buttons.contains(&"CPU: < Normal >".to_string()));
+}
+
+#[test]
+fn texts_on_screen_follow_the_language() {
+    let mut game = Game::at_title();
+    game.app().world_mut().resource_mut::<Settings>().language = Language::Russian;
+    game.update();
+    let buttons = game.buttons();
+    assert!(buttons.contains(&"Играть".to_string()));
+    assert!(buttons.contains(&"Титры".to_string()));
+    // Including options, which are looked up anew.
+    game.click("CPU: < Нормально >");
+    assert!(game.buttons().contains(&"CPU: < Сложно >".to_string()));
+
+    game.app().world_mut().resource_mut::<Settings>().language = Language::English;
+    game.update();
+    let buttons = game.buttons();
+    assert!(buttons.contains(&"Play".to_string()));
+    assert!(buttons.contains(&"Credits".to_string()));
+    assert!(buttons.contains(&"CPU: < Hard >".to_string()));
+}
+
+#[test]
+fn connection_errors_follow_the_language() {
+    let mut game = Game::at_title();
+    game.click("Online");
+    game.click("Host");
+    for _ in 0.."7000".len() {
+        game.tap(KeyCode::Backspace);
+    }
+    game.type_text("99999");
+    game.click("Host");
+    assert!(texts(&mut game).contains(&"99999 is not a port".to_string()));
+
+    game.app().world_mut().resource_mut::<Settings>().language = Language::German;
+    game.update();
+    assert!(texts(&mut game).contains(&"99999 ist kein Port".to_string()));
+}
+
+#[test]
+fn failed_address_lookups_follow_the_language() {
+    let mut game = Game::at_title();
+    game.click("Online");
+    game.click("Join");
+    for _ in 0.."127.0.0.1:7000".len() {
+        game.tap(KeyCode::Backspace);
+    }
+    // Without a port, there's nothing to look up, but the lookup still runs in the background.
+    game.type_text("localhost");
+    game.click("Join");
+    for _ in 0..100 {
+        if texts(&mut game).contains(&"Can't find localhost".to_string()) {
+            break;
+        }
+        game.update();
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(texts(&mut game).contains(&"Can't find localhost".to_string()));
+
+    game.app().world_mut().resource_mut::<Settings>().language = Language::Spanish;
+    game.update();
+    assert!(texts(&mut game).contains(&"No se encuentra localhost".to_string()));
+}
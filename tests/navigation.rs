@@ -0,0 +1,72 @@
+//! Using menus without a mouse, running headless, see [`bevy_quickstart::headless`].
+
+use bevy::prelude::*;
+use bevy_quickstart::headless::{Game, Screen};
+
+/// Stacks the screen's widgets top to bottom like the UI layout would,
+/// which doesn't run headless.
+fn lay_out(game: &mut Game) {
+    let world = game.app().world_mut();
+    let mut root_query = world.query_filtered::<&Children, (With<Node>, Without<Parent>)>();
+    let widgets: Vec<Entity> = root_query
+        .iter(world)
+        .flat_map(|children| children.iter().copied())
+        .collect();
+    for (row, widget) in widgets.into_iter().enumerate() {
+        if let Some(mut transform) = world.get_mut::<Transform>(widget) {
+            transform.translation.y = 100.0 * row as f32;
+        }
+    }
+    game.update();
+}
+
+#[test]
+fn arrows_move_focus_and_enter_presses() {
+    let mut game = Game::at_title();
+    game.click("Online");
+    assert_eq!(game.screen(), Screen::Lobby);
+    lay_out(&mut game);
+
+    // Host, then Join.
+    game.tap(KeyCode::ArrowDown);
+    game.tap(KeyCode::ArrowDown);
+    game.tap(KeyCode::Enter);
+    assert_eq!(game.screen(), Screen::Join);
+}
+
+#[test]
+fn focus_stops_at_the_edges() {
+    let mut game = Game::at_title();
+    game.click("Online");
+    lay_out(&mut game);
+
+    // Host, and nothing above it.
+    game.tap(KeyCode::ArrowDown);
+    game.tap(KeyCode::ArrowUp);
+    game.tap(KeyCode::Enter);
+    assert_eq!(game.screen(), Screen::Host);
+}
+
+#[test]
+fn escape_presses_back() {
+    let mut game = Game::at_title();
+    game.click("Credits");
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.screen(), Screen::Title);
+
+    game.click("Online");
+    game.click("Join");
+    assert_eq!(game.screen(), Screen::Join);
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.screen(), Screen::Lobby);
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.screen(), Screen::Title);
+}
+
+#[test]
+fn escape_on_the_title_screen_does_nothing() {
+    let mut game = Game::at_title();
+    game.tap(KeyCode::Escape);
+    game.update();
+    assert_eq!(game.screen(), Screen::Title);
+}
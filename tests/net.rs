@@ -0,0 +1,121 @@
+//! Networked matches between two headless games on one machine,
+//! see [`bevy_quickstart::headless`].
+
+use std::{net::Ipv4Addr, thread, time::Duration};
+
+use bevy::prelude::*;
+use bevy_quickstart::headless::{
+    Health, KeyBindings, MatchResults, NetConnection, Player, RoundOutcome, Screen, Simulation,
+    SpawnPlayer,
+};
+
+/// Both sides control their own player with these.
+const KEYS: KeyBindings = KeyBindings::WASD;
+
+/// How many updates each side gets to find the other before giving up.
+const CONNECT_ATTEMPTS: usize = 500;
+
+/// Two players facing each other within punching distance, with Player 2 one punch from going down.
+fn face_off(world: &mut World) {
+    for (player, x) in [(Player::Player1, -60.0), (Player::Player2, 60.0)] {
+        world.trigger(SpawnPlayer {
+            player,
+            translation: Vec2::new(x, -200.0),
+        });
+    }
+    world.flush();
+    let mut query = world.query::<(&Player, &mut Health)>();
+    for (player, mut health) in query.iter_mut(world) {
+        if *player == Player::Player2 {
+            health.current = 5.0;
+        }
+    }
+}
+
+/// A host and a joining player on 127.0.0.1, both in the same match.
+fn connect() -> (Simulation, Simulation) {
+    let host = NetConnection::host(0).unwrap();
+    let port = host.local_port().unwrap();
+    let join = NetConnection::join((Ipv4Addr::LOCALHOST, port).into()).unwrap();
+    let mut host = Simulation::online(host);
+    let mut join = Simulation::online(join);
+    for simulation in [&mut host, &mut join] {
+        simulation
+            .app()
+            .add_systems(OnEnter(Screen::Playing), face_off);
+    }
+
+    for _ in 0..CONNECT_ATTEMPTS {
+        if host.screen() == Screen::Playing && join.screen() == Screen::Playing {
+            return (host, join);
+        }
+        run_both(&mut host, &mut join, 1);
+    }
+    panic!("The host and the joining player never started a match");
+}
+
+/// Runs updates on both sides in turns, giving messages time to arrive.
+fn run_both(host: &mut Simulation, join: &mut Simulation, updates: u32) {
+    for _ in 0..updates {
+        host.tick();
+        join.tick();
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// Checks that both sides agree on everything about the players and the match.
+fn assert_in_sync(host: &mut Simulation, join: &mut Simulation) {
+    for player in [Player::Player1, Player::Player2] {
+        assert_eq!(host.health(player), join.health(player), "{player:?}");
+        assert_eq!(host.state(player), join.state(player), "{player:?}");
+        assert_eq!(host.position(player), join.position(player), "{player:?}");
+    }
+    assert_eq!(outcome(host), outcome(join));
+    assert_eq!(announcements(host), announcements(join));
+}
+
+/// How many times the end of the round is announced on screen.
+fn announcements(simulation: &mut Simulation) -> usize {
+    let world = simulation.app().world_mut();
+    world
+        .query::<&Name>()
+        .iter(world)
+        .filter(|name| name.as_str() == "Round Over")
+        .count()
+}
+
+fn outcome(simulation: &mut Simulation) -> Option<RoundOutcome> {
+    simulation.app().world().resource::<MatchResults>().outcome
+}
+
+#[test]
+fn both_sides_play_out_the_same_match() {
+    let (mut host, mut join) = connect();
+    run_both(&mut host, &mut join, 10);
+
+    // The host runs ahead, guessing that the other player does nothing instead of blocking.
+    host.press(KEYS.attack);
+    join.press(KEYS.block);
+    for _ in 0..12 {
+        host.tick();
+    }
+    host.release(KEYS.attack);
+    run_both(&mut host, &mut join, 120);
+    assert_in_sync(&mut host, &mut join);
+    let health = host.health(Player::Player2);
+    assert!(
+        health > 0.0 && health < 5.0,
+        "blocking left {health} health"
+    );
+
+    // Without blocking, the next punch ends the round.
+    join.release(KEYS.block);
+    run_both(&mut host, &mut join, 10);
+    host.press(KEYS.attack);
+    run_both(&mut host, &mut join, 2);
+    host.release(KEYS.attack);
+    run_both(&mut host, &mut join, 100);
+    assert_in_sync(&mut host, &mut join);
+    assert_eq!(outcome(&mut host), Some(RoundOutcome::Won(Player::Player1)));
+    assert_eq!(announcements(&mut host), 1);
+}
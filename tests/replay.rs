@@ -0,0 +1,52 @@
+//! Watching recorded matches, running headless, see [`bevy_quickstart::headless`].
+
+use bevy::prelude::*;
+use bevy_quickstart::headless::{Game, Screen};
+
+/// How many updates of the match are recorded and then compared.
+const UPDATES: usize = 180;
+
+/// Where every grunt is, from left to right.
+fn grunts(game: &mut Game) -> Vec<Vec2> {
+    let world = game.app().world_mut();
+    let mut grunts: Vec<_> = world
+        .query::<(&Name, &Transform)>()
+        .iter(world)
+        .filter(|(name, _)| name.as_str() == "Grunt")
+        .map(|(_, transform)| transform.translation.xy())
+        .collect();
+    grunts.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    grunts
+}
+
+/// Keeps the world camera somewhere the players aren't, like shake or a slow frame rate could.
+fn move_camera(mut camera_query: Query<(&Name, &mut Transform)>) {
+    for (name, mut transform) in &mut camera_query {
+        if name.as_str() == "World Camera" {
+            transform.translation.x = 1000.0;
+        }
+    }
+}
+
+#[test]
+fn co_op_replays_send_in_the_same_grunts() {
+    let mut game = Game::at_title();
+    game.click("Co-op");
+    assert_eq!(game.screen(), Screen::Playing);
+    for _ in 0..UPDATES {
+        game.update();
+    }
+    let played = grunts(&mut game);
+    assert!(!played.is_empty());
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.screen(), Screen::Title);
+
+    // Before the gameplay of every frame.
+    game.app().add_systems(First, move_camera);
+    game.click("Replay");
+    assert_eq!(game.screen(), Screen::Playing);
+    for _ in 0..UPDATES {
+        game.update();
+    }
+    assert_eq!(grunts(&mut game), played);
+}
@@ -0,0 +1,149 @@
+//! Moving between screens, running headless, see [`bevy_quickstart::headless`].
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_quickstart::headless::{Game, Level, Player, Screen};
+
+/// How long the splash screen shows.
+const SPLASH_DURATION: Duration = Duration::from_millis(1800);
+
+/// How many players are in the arena, not counting their HUDs.
+fn player_count(game: &mut Game) -> usize {
+    let world = game.app().world_mut();
+    world
+        .query_filtered::<(), (With<Player>, With<Sprite>)>()
+        .iter(world)
+        .count()
+}
+
+#[test]
+fn splash_moves_on_to_loading() {
+    let mut game = Game::new();
+    assert_eq!(game.screen(), Screen::Splash);
+
+    game.run_for(SPLASH_DURATION / 2);
+    assert_eq!(game.screen(), Screen::Splash);
+
+    game.run_for(SPLASH_DURATION / 2);
+    game.update();
+    assert_eq!(game.screen(), Screen::Loading);
+}
+
+#[test]
+fn loading_waits_for_assets() {
+    let mut game = Game::new();
+    game.run_for(SPLASH_DURATION);
+    game.update();
+    assert_eq!(game.screen(), Screen::Loading);
+
+    game.run_for(Duration::from_secs(1));
+    assert_eq!(game.screen(), Screen::Loading);
+
+    game.finish_loading();
+    game.update();
+    game.update();
+    assert_eq!(game.screen(), Screen::Title);
+}
+
+#[test]
+fn play_starts_a_versus_match() {
+    let mut game = Game::at_title();
+    game.click("Play");
+    assert_eq!(game.screen(), Screen::Playing);
+    assert_eq!(player_count(&mut game), 2);
+}
+
+#[test]
+fn play_vs_cpu_starts_a_match() {
+    let mut game = Game::at_title();
+    game.click("Play vs CPU");
+    assert_eq!(game.screen(), Screen::Playing);
+    assert_eq!(player_count(&mut game), 2);
+}
+
+#[test]
+fn co_op_starts_a_match() {
+    let mut game = Game::at_title();
+    game.click("Co-op");
+    assert_eq!(game.screen(), Screen::Playing);
+    assert_eq!(player_count(&mut game), 2);
+}
+
+#[test]
+fn difficulty_cycles_in_place() {
+    let mut game = Game::at_title();
+    let before = game.buttons();
+    let difficulty = before
+        .iter()
+        .find(|label| label.starts_with("CPU: "))
+        .unwrap()
+        .clone();
+
+    game.click(&difficulty);
+    assert_eq!(game.screen(), Screen::Title);
+    let after = game.buttons();
+    assert!(!after.contains(&difficulty));
+    assert_eq!(before.len(), after.len());
+}
+
+#[test]
+fn title_opens_other_screens() {
+    for (button, screen) in [
+        ("Credits", Screen::Credits),
+        ("High Scores", Screen::HighScores),
+        ("Online", Screen::Lobby),
+    ] {
+        let mut game = Game::at_title();
+        game.click(button);
+        assert_eq!(game.screen(), screen, "after clicking {button:?}");
+    }
+}
+
+#[test]
+fn exit_quits() {
+    let mut game = Game::at_title();
+    game.click("Exit");
+    assert!(!game.app().world().resource::<Events<AppExit>>().is_empty());
+}
+
+#[test]
+fn credits_back_returns_to_title() {
+    let mut game = Game::at_title();
+    game.click("Credits");
+    assert_eq!(game.screen(), Screen::Credits);
+
+    game.click("Back");
+    assert_eq!(game.screen(), Screen::Title);
+    assert!(game.buttons().contains(&"Play".to_string()));
+}
+
+#[test]
+fn escape_returns_from_playing_to_title() {
+    let mut game = Game::at_title();
+    game.click("Play");
+    game.run_for(Duration::from_secs(1));
+    assert_eq!(game.screen(), Screen::Playing);
+
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.screen(), Screen::Title);
+    assert_eq!(player_count(&mut game), 0);
+}
+
+#[test]
+fn continue_resumes_the_saved_level() {
+    let mut game = Game::at_title();
+    assert!(!game.buttons().contains(&"Continue".to_string()));
+    game.click("Co-op");
+    game.run_for(Duration::from_secs(1));
+    game.tap(KeyCode::Escape);
+    assert_eq!(game.screen(), Screen::Title);
+
+    // Whatever was played last, the saved level comes back.
+    game.app().world_mut().insert_resource(Level::Arena);
+    game.click("Continue");
+    game.update();
+    assert_eq!(game.screen(), Screen::Playing);
+    assert_eq!(*game.app().world().resource::<Level>(), Level::Street);
+    assert_eq!(player_count(&mut game), 2);
+}
@@ -0,0 +1,160 @@
+//! Gameplay tests running headless, see [`bevy_quickstart::headless`].
+
+use bevy::prelude::*;
+use bevy_quickstart::headless::{
+    AttackKind, CharacterState, GameMode, Health, KeyBindings, MatchResults, Player, Screen,
+    Simulation,
+};
+
+const KEYS: KeyBindings = KeyBindings::WASD;
+
+/// Two players facing each other within punching distance.
+fn face_off() -> Simulation {
+    let mut simulation = Simulation::new();
+    simulation.spawn_player(Player::Player1, Vec2::new(-60.0, -200.0));
+    simulation.spawn_player(Player::Player2, Vec2::new(60.0, -200.0));
+    simulation.tick();
+    simulation
+}
+
+#[test]
+fn players_stand_where_they_spawned() {
+    let mut simulation = face_off();
+    simulation.run_ticks(10);
+
+    assert_eq!(
+        simulation.position(Player::Player1),
+        Vec2::new(-60.0, -200.0)
+    );
+    assert_eq!(
+        simulation.position(Player::Player2),
+        Vec2::new(60.0, -200.0)
+    );
+    assert_eq!(simulation.state(Player::Player1), CharacterState::Idle);
+    assert_eq!(simulation.screen(), Screen::Playing);
+}
+
+#[test]
+fn holding_a_direction_walks() {
+    let mut simulation = face_off();
+    let start = simulation.position(Player::Player1);
+
+    simulation.press(KEYS.left);
+    simulation.run_ticks(30);
+    assert_eq!(simulation.state(Player::Player1), CharacterState::Walk);
+    let walked = simulation.position(Player::Player1);
+    assert!(walked.x < start.x, "{walked} isn't left of {start}");
+    assert_eq!(walked.y, start.y);
+
+    simulation.release(KEYS.left);
+    simulation.run_ticks(5);
+    assert_eq!(simulation.state(Player::Player1), CharacterState::Idle);
+    let stopped = simulation.position(Player::Player1);
+    simulation.run_ticks(10);
+    assert_eq!(simulation.position(Player::Player1), stopped);
+}
+
+#[test]
+fn running_is_faster_than_walking() {
+    let distance = |run: bool| {
+        let mut simulation = face_off();
+        let start = simulation.position(Player::Player1);
+        if run {
+            simulation.press(KEYS.run);
+        }
+        simulation.press(KEYS.down);
+        simulation.run_ticks(20);
+        start.distance(simulation.position(Player::Player1))
+    };
+
+    assert!(distance(true) > distance(false));
+}
+
+#[test]
+fn punching_hurts_the_opponent() {
+    let mut simulation = face_off();
+
+    simulation.tap(KEYS.attack);
+    simulation.run_ticks(2);
+    assert_eq!(
+        simulation.state(Player::Player1),
+        CharacterState::Attack(AttackKind::Punch)
+    );
+
+    simulation.run_ticks(10);
+    assert_eq!(simulation.health(Player::Player2), 92.0);
+    assert_eq!(simulation.state(Player::Player2), CharacterState::HitStun);
+    assert_eq!(simulation.health(Player::Player1), 100.0);
+
+    simulation.run_ticks(60);
+    assert_eq!(simulation.state(Player::Player1), CharacterState::Idle);
+    assert_eq!(simulation.state(Player::Player2), CharacterState::Idle);
+}
+
+#[test]
+fn every_player_has_results_before_scoring() {
+    let mut simulation = face_off();
+    let results = simulation.app().world().resource::<MatchResults>();
+    let players: Vec<Player> = results.players.iter().map(|row| row.player).collect();
+    assert_eq!(players, [Player::Player1, Player::Player2]);
+    assert!(results.players.iter().all(|row| row.score == 0));
+}
+
+#[test]
+fn blocking_reduces_damage() {
+    let mut simulation = face_off();
+
+    simulation.press(KeyBindings::ARROWS.block);
+    simulation.run_ticks(2);
+    assert_eq!(simulation.state(Player::Player2), CharacterState::Block);
+
+    simulation.tap(KEYS.attack);
+    simulation.run_ticks(15);
+    let health = simulation.health(Player::Player2);
+    assert!(
+        health > 92.0 && health < 100.0,
+        "blocking left {health} health"
+    );
+}
+
+#[test]
+fn knockout_ends_the_round() {
+    let mut simulation = face_off();
+    let world = simulation.app().world_mut();
+    let mut query = world.query::<(&Player, &mut Health)>();
+    for (player, mut health) in query.iter_mut(world) {
+        if *player == Player::Player2 {
+            health.current = 1.0;
+        }
+    }
+
+    simulation.tap(KEYS.attack);
+    simulation.run_ticks(15);
+    assert_eq!(simulation.health(Player::Player2), 0.0);
+    assert_eq!(
+        simulation.state(Player::Player2),
+        CharacterState::KnockedDown
+    );
+
+    // Characters out of health stay down.
+    simulation.run_ticks(60);
+    assert_eq!(simulation.state(Player::Player2), CharacterState::Dead);
+    assert_eq!(simulation.screen(), Screen::Playing);
+
+    // The outcome is shown for a while before the results.
+    simulation.run_ticks(5 * 60);
+    assert_eq!(simulation.screen(), Screen::Results);
+}
+
+#[test]
+fn co_op_partners_cant_grab_each_other() {
+    let grab = |mode: GameMode| {
+        let mut simulation = face_off();
+        simulation.app().insert_resource(mode);
+        simulation.press(KEYS.grab);
+        simulation.run_ticks(5);
+        simulation.state(Player::Player2)
+    };
+    assert_eq!(grab(GameMode::Versus), CharacterState::Grabbed);
+    assert_ne!(grab(GameMode::Waves), CharacterState::Grabbed);
+}
@@ -0,0 +1,76 @@
+//! Styling menus from the UI theme, running headless, see [`bevy_quickstart::headless`].
+
+use std::{thread, time::Duration};
+
+use bevy::prelude::*;
+use bevy_quickstart::headless::{Game, Theme};
+
+/// The font sizes of the buttons' texts, and the buttons' widths.
+/// Buttons that show a value, like the difficulty selector, are wider than the others.
+fn button_styles(game: &mut Game) -> Vec<(f32, Val)> {
+    let world = game.app().world_mut();
+    let mut text_query = world.query::<(&Text, &Parent)>();
+    let mut button_query = world.query_filtered::<&Style, With<Button>>();
+    text_query
+        .iter(world)
+        .filter_map(|(text, parent)| {
+            let style = button_query.get(world, parent.get()).ok()?;
+            Some((text.sections[0].style.font_size, style.width))
+        })
+        .collect()
+}
+
+#[test]
+fn the_theme_file_loads() {
+    let mut game = Game::new();
+    let handle: Handle<Theme> = game
+        .app()
+        .world()
+        .resource::<AssetServer>()
+        .load("ui.theme.ron");
+    for _ in 0..200 {
+        game.update();
+        let world = game.app().world();
+        if world
+            .resource::<AssetServer>()
+            .is_loaded_with_dependencies(&handle)
+        {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    // The theme in use is taken from the file once it has loaded.
+    game.update();
+
+    let world = game.app().world();
+    let loaded = world.resource::<Assets<Theme>>().get(&handle).unwrap();
+    let current = world.resource::<Theme>();
+    assert_eq!(loaded.button_width, current.button_width);
+    assert_eq!(loaded.button_text.size, current.button_text.size);
+    assert_eq!(loaded.node_background, current.node_background);
+}
+
+#[test]
+fn menus_follow_the_theme() {
+    let mut game = Game::at_title();
+    let theme = game.app().world().resource::<Theme>().clone();
+    let styles = button_styles(&mut game);
+    assert!(!styles.is_empty());
+    assert!(styles
+        .iter()
+        .all(|&(font_size, _)| font_size == theme.button_text.size));
+    assert!(styles
+        .iter()
+        .all(|&(_, width)| [theme.button_width, theme.wide_width]
+            .map(Val::Px)
+            .contains(&width)));
+
+    let mut theme = game.app().world_mut().resource_mut::<Theme>();
+    theme.button_text.size = 20.0;
+    theme.button_width = 300.0;
+    game.update();
+    let styles = button_styles(&mut game);
+    assert!(styles.iter().all(|&(font_size, _)| font_size == 20.0));
+    assert!(styles.iter().any(|&(_, width)| width == Val::Px(300.0)));
+    assert!(!styles.iter().any(|&(_, width)| width == Val::Px(200.0)));
+}
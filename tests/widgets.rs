@@ -0,0 +1,257 @@
+//! Using the UI widgets on the game's screens, running headless,
+//! see [`bevy_quickstart::headless`].
+
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::RelativeCursorPosition,
+};
+use bevy_quickstart::headless::{
+    Containers, Focused, Game, Screen, ScrollList, Slider, Toggle, Widgets,
+};
+
+/// The text in the screen's text field, which is the button that ends in a caret or its gap.
+fn text_field(game: &mut Game) -> String {
+    let label = game
+        .buttons()
+        .into_iter()
+        .find(|label| label.ends_with(['|', ' ']))
+        .unwrap();
+    label[..label.len() - 1].to_string()
+}
+
+/// Spawns a widget on top of the title screen, since not every kind of widget is on a screen.
+fn spawn_widget(game: &mut Game, spawn: impl FnOnce(&mut ChildBuilder) -> Entity) -> Entity {
+    let world = game.app().world_mut();
+    let mut widget = Entity::PLACEHOLDER;
+    world.commands().ui_root().with_children(|children| {
+        widget = spawn(children);
+    });
+    world.flush();
+    game.update();
+    widget
+}
+
+/// The text shown on a widget.
+fn widget_text(game: &mut Game, widget: Entity) -> String {
+    let world = game.app().world_mut();
+    let children = world.get::<Children>(widget).unwrap().to_vec();
+    let mut text_query = world.query::<&Text>();
+    let text = text_query.iter_many(world, &children).next().unwrap();
+    text.sections
+        .iter()
+        .map(|section| &*section.value)
+        .collect()
+}
+
+/// Presses `widget` with the mouse at `cursor`, relative to its size, for one frame.
+fn press_at(game: &mut Game, widget: Entity, cursor: Vec2) {
+    let mut entity = game.app().world_mut().entity_mut(widget);
+    if let Some(mut position) = entity.get_mut::<RelativeCursorPosition>() {
+        position.normalized = Some(cursor);
+    }
+    *entity.get_mut::<Interaction>().unwrap() = Interaction::Pressed;
+    game.update();
+    *game
+        .app()
+        .world_mut()
+        .get_mut::<Interaction>(widget)
+        .unwrap() = Interaction::None;
+    game.update();
+}
+
+fn focus(game: &mut Game, widget: Entity) {
+    let world = game.app().world_mut();
+    let focused: Vec<_> = world
+        .query_filtered::<Entity, With<Focused>>()
+        .iter(world)
+        .collect();
+    for entity in focused {
+        world.entity_mut(entity).remove::<Focused>();
+    }
+    world.entity_mut(widget).insert(Focused);
+}
+
+#[test]
+fn dragging_a_slider_snaps_to_a_step() {
+    let mut game = Game::at_title();
+    let slider = spawn_widget(&mut game, |children| {
+        children
+            .slider("Volume", Slider::new(50.0, 0.0, 100.0, 10.0))
+            .id()
+    });
+    assert_eq!(widget_text(&mut game, slider), "Volume: 50");
+
+    press_at(&mut game, slider, Vec2::new(0.74, 0.5));
+    assert_eq!(widget_text(&mut game, slider), "Volume: 70");
+    press_at(&mut game, slider, Vec2::new(1.3, 0.5));
+    assert_eq!(widget_text(&mut game, slider), "Volume: 100");
+}
+
+#[test]
+fn arrow_keys_step_a_focused_slider_within_its_range() {
+    let mut game = Game::at_title();
+    let slider = spawn_widget(&mut game, |children| {
+        children
+            .slider("Volume", Slider::new(0.5, 0.0, 1.0, 0.25))
+            .id()
+    });
+    focus(&mut game, slider);
+
+    game.tap(KeyCode::ArrowRight);
+    assert_eq!(widget_text(&mut game, slider), "Volume: 0.75");
+    for _ in 0..6 {
+        game.tap(KeyCode::ArrowRight);
+    }
+    assert_eq!(widget_text(&mut game, slider), "Volume: 1.00");
+    for _ in 0..12 {
+        game.tap(KeyCode::ArrowLeft);
+    }
+    assert_eq!(widget_text(&mut game, slider), "Volume: 0.00");
+}
+
+#[test]
+fn stepping_a_slider_stays_on_its_steps() {
+    let mut game = Game::at_title();
+    let slider = spawn_widget(&mut game, |children| {
+        children
+            .slider("Volume", Slider::new(0.0, 0.0, 10.0, 0.1))
+            .id()
+    });
+    focus(&mut game, slider);
+
+    for _ in 0..30 {
+        game.tap(KeyCode::ArrowRight);
+    }
+    let value = game.app().world().get::<Slider>(slider).unwrap().value;
+    assert_eq!(value, 3.0);
+    assert_eq!(widget_text(&mut game, slider), "Volume: 3.0");
+}
+
+#[test]
+fn enter_leaves_a_focused_slider_where_it_is() {
+    let mut game = Game::at_title();
+    let slider = spawn_widget(&mut game, |children| {
+        children
+            .slider("Volume", Slider::new(50.0, 0.0, 100.0, 10.0))
+            .id()
+    });
+    focus(&mut game, slider);
+    // The mouse is somewhere over the slider, but not the one pressing it.
+    game.app()
+        .world_mut()
+        .get_mut::<RelativeCursorPosition>(slider)
+        .unwrap()
+        .normalized = Some(Vec2::new(0.9, 0.5));
+
+    game.tap(KeyCode::Enter);
+    assert_eq!(widget_text(&mut game, slider), "Volume: 50");
+}
+
+#[test]
+fn clicking_a_toggle_flips_it() {
+    let mut game = Game::at_title();
+    let toggle = spawn_widget(&mut game, |children| {
+        children.toggle("Fullscreen", false).id()
+    });
+    assert_eq!(widget_text(&mut game, toggle), "[ ] Fullscreen");
+
+    press_at(&mut game, toggle, Vec2::ZERO);
+    assert_eq!(widget_text(&mut game, toggle), "[x] Fullscreen");
+    assert_eq!(
+        game.app().world().get::<Toggle>(toggle),
+        Some(&Toggle(true))
+    );
+    press_at(&mut game, toggle, Vec2::ZERO);
+    assert_eq!(widget_text(&mut game, toggle), "[ ] Fullscreen");
+}
+
+#[test]
+fn scroll_lists_stop_at_either_end() {
+    let mut game = Game::at_title();
+    let list = spawn_widget(&mut game, |children| {
+        children
+            .scroll_list(Val::Px(40.0))
+            .with_children(|children| {
+                for line in ["One", "Two", "Three", "Four"] {
+                    children.label(line);
+                }
+            })
+            .id()
+    });
+    game.app()
+        .world_mut()
+        .get_mut::<RelativeCursorPosition>(list)
+        .unwrap()
+        .normalized = Some(Vec2::splat(0.5));
+    let scroll = |game: &mut Game, lines: f32| {
+        game.app().world_mut().send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: lines,
+            window: Entity::PLACEHOLDER,
+        });
+        game.update();
+        game.app().world().get::<ScrollList>(list).unwrap().offset
+    };
+
+    // Scrolling down goes no further than the last item, far short of a hundred lines.
+    let bottom = scroll(&mut game, -100.0);
+    assert!((0.0..1000.0).contains(&bottom), "scrolled to {bottom}");
+    assert_eq!(scroll(&mut game, -100.0), bottom);
+    let world = game.app().world_mut();
+    let children = world.get::<Children>(list).unwrap().to_vec();
+    let mut style_query = world.query::<&Style>();
+    for style in style_query.iter_many(world, &children) {
+        assert_eq!(style.top, Val::Px(-bottom));
+    }
+
+    // Scrolling up goes no further than the first item.
+    assert_eq!(scroll(&mut game, 100.0), 0.0);
+}
+
+#[test]
+fn difficulty_selector_cycles_through_levels() {
+    let mut game = Game::at_title();
+    let selector = |game: &mut Game| {
+        game.buttons()
+            .into_iter()
+            .find(|label| label.starts_with("CPU: "))
+            .unwrap()
+    };
+    assert_eq!(selector(&mut game), "CPU: < Normal >");
+
+    game.click("CPU: < Normal >");
+    assert_eq!(selector(&mut game), "CPU: < Hard >");
+    game.click("CPU: < Hard >");
+    assert_eq!(selector(&mut game), "CPU: < Easy >");
+}
+
+#[test]
+fn host_port_takes_digits_only() {
+    let mut game = Game::at_title();
+    game.click("Online");
+    game.click("Host");
+    assert_eq!(game.screen(), Screen::Host);
+    assert_eq!(text_field(&mut game), "7000");
+
+    for _ in 0..4 {
+        game.tap(KeyCode::Backspace);
+    }
+    game.type_text("80a8.0");
+    assert_eq!(text_field(&mut game), "8080");
+}
+
+#[test]
+fn join_address_takes_host_names() {
+    let mut game = Game::at_title();
+    game.click("Online");
+    game.click("Join");
+    assert_eq!(game.screen(), Screen::Join);
+
+    for _ in 0.."127.0.0.1:7000".len() {
+        game.tap(KeyCode::Backspace);
+    }
+    game.type_text("local host:7000");
+    assert_eq!(text_field(&mut game), "localhost:7000");
+}